@@ -0,0 +1,572 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures_util::stream::{BoxStream, TryStreamExt};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+use crate::api::utils::files::{temp_path, write_atomically};
+use crate::api::utils::{audio_stream, create_request, log_info, paginate, send_with_retry, write_stream, AudioStream};
+use crate::config::Config;
+use crate::error::Error;
+
+/// An item of the generation history: one request and the audio it produced.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryItem {
+    pub history_item_id: String,
+    pub request_id: Option<String>,
+    pub voice_id: Option<String>,
+    pub voice_name: Option<String>,
+    pub voice_category: Option<String>,
+    pub model_id: Option<String>,
+    /// The text that was converted, if any.
+    pub text: Option<String>,
+    pub date_unix: i64,
+    /// The character count of the account before the request.
+    pub character_count_change_from: Option<i64>,
+    /// The character count of the account after the request.
+    pub character_count_change_to: Option<i64>,
+    /// The MIME type of the generated audio, e.g. `audio/mpeg`.
+    pub content_type: Option<String>,
+    pub state: Option<String>,
+    /// The voice settings the audio was generated with.
+    pub settings: Option<serde_json::Value>,
+    /// The API the item was generated with, e.g. `TTS` or `STS`.
+    pub source: Option<String>,
+}
+
+impl HistoryItem {
+    /// Returns the number of characters the request was billed for.
+    pub fn character_count(&self) -> Option<i64> {
+        Some(self.character_count_change_to? - self.character_count_change_from?)
+    }
+}
+
+/// Query parameters of the history listing.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct HistoryParams {
+    /// The number of items per page, at most 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    /// Lists the items older than this one, from `HistoryPage::last_history_item_id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_after_history_item_id: Option<String>,
+    /// Only lists items generated with this voice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice_id: Option<String>,
+}
+
+/// A page of the generation history, newest first.
+#[derive(Deserialize, Debug, Clone)]
+pub struct HistoryPage {
+    pub history: Vec<HistoryItem>,
+    pub last_history_item_id: Option<String>,
+    pub has_more: bool,
+}
+
+/// The outcome of [`HistoryClient::sync_history`].
+#[derive(Debug, Default, Clone)]
+pub struct SyncReport {
+    /// The items downloaded by this run.
+    pub downloaded: usize,
+    /// The items that were already mirrored.
+    pub skipped: usize,
+    /// The items whose audio was already mirrored but whose metadata file had to be rewritten.
+    pub repaired: usize,
+}
+
+/// The name of the file that records which items have been mirrored, one ID per line.
+pub const SYNC_STATE_FILE: &str = ".history-sync.log";
+
+/// The items mirrored so far. Each item is appended to the log as soon as it is mirrored, so
+/// an interrupted sync can resume without the whole record being rewritten after every item.
+struct SyncLog {
+    synced: HashSet<String>,
+    file: tokio::fs::File,
+}
+
+impl SyncLog {
+    async fn open(path: &Path) -> Result<Self, Error> {
+        let contents = match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+        // A last line without its newline was cut short by a crash, so it is dropped.
+        let complete = &contents[..contents.rfind('\n').map_or(0, |end| end + 1)];
+        let synced = complete
+            .lines()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        if complete.len() < contents.len() {
+            write_atomically(path, complete.as_bytes()).await?;
+        }
+        let file = tokio::fs::OpenOptions::new().create(true).append(true).open(path).await?;
+        Ok(SyncLog { synced, file })
+    }
+
+    fn contains(&self, history_item_id: &str) -> bool {
+        self.synced.contains(history_item_id)
+    }
+
+    async fn record(&mut self, history_item_id: &str) -> Result<(), Error> {
+        if self.synced.insert(history_item_id.to_string()) {
+            self.file.write_all(format!("{}\n", history_item_id).as_bytes()).await?;
+            self.file.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+/// The files an item is mirrored to.
+struct SyncTarget {
+    audio_file: String,
+    audio_path: PathBuf,
+    sidecar_path: PathBuf,
+}
+
+/// What a sync does with an item.
+#[derive(Debug, PartialEq, Eq)]
+enum SyncAction {
+    /// The item is fully mirrored.
+    Skip,
+    /// The audio is mirrored but the metadata file has to be rewritten.
+    Repair,
+    /// The audio has to be downloaded.
+    Download,
+}
+
+impl SyncTarget {
+    fn new(dest_dir: &Path, item: &HistoryItem) -> Self {
+        let audio_file = format!("{}.{}", item.history_item_id, audio_extension(item.content_type.as_deref()));
+        SyncTarget {
+            audio_path: dest_dir.join(&audio_file),
+            sidecar_path: dest_dir.join(format!("{}.json", item.history_item_id)),
+            audio_file,
+        }
+    }
+
+    /// Decides what to do with `item`. Files of items missing from the log are not trusted,
+    /// since they may be left over from a run that crashed before recording the item.
+    async fn action(&self, log: &SyncLog, item: &HistoryItem) -> Result<SyncAction, Error> {
+        if !log.contains(&item.history_item_id) || !tokio::fs::try_exists(&self.audio_path).await? {
+            Ok(SyncAction::Download)
+        } else if !tokio::fs::try_exists(&self.sidecar_path).await? {
+            Ok(SyncAction::Repair)
+        } else {
+            Ok(SyncAction::Skip)
+        }
+    }
+
+    /// Writes the metadata file of `item`.
+    async fn write_sidecar(&self, item: &HistoryItem) -> Result<(), Error> {
+        let sidecar = Sidecar {
+            item,
+            character_count: item.character_count(),
+            audio_file: &self.audio_file,
+        };
+        write_atomically(&self.sidecar_path, &serde_json::to_vec_pretty(&sidecar)?).await
+    }
+}
+
+/// The JSON file written next to the audio of every mirrored item.
+#[derive(Serialize)]
+struct Sidecar<'a> {
+    #[serde(flatten)]
+    item: &'a HistoryItem,
+    character_count: Option<i64>,
+    audio_file: &'a str,
+}
+
+/// The request payload of the bulk download.
+#[derive(Serialize)]
+struct DownloadRequest<'a> {
+    history_item_ids: &'a [String],
+}
+
+/// Client for interacting with the ElevenLabs History API.
+#[derive(Clone)]
+pub struct HistoryClient {
+    client: Client,
+    config: Arc<Config>,
+}
+
+impl HistoryClient {
+    /// Creates a new `HistoryClient` using the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A `Config` instance containing the necessary configuration.
+    pub fn new(config: Config) -> Self {
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `HistoryClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
+        HistoryClient { client, config }
+    }
+
+    /// Fetches a single page of the generation history.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The page size, cursor and voice filter.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `HistoryPage`, or `Error` on failure.
+    pub async fn get_history(&self, params: &HistoryParams) -> Result<HistoryPage, Error> {
+        let url = format!("{}/v1/history", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .query(params))
+        })
+        .await?;
+
+        Ok(response.json::<HistoryPage>().await?)
+    }
+
+    /// Streams every history item matching `params`, newest first, fetching further pages as
+    /// the stream is consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, history::{HistoryClient, HistoryParams}};
+    /// # use futures_util::TryStreamExt;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let history_client = HistoryClient::new(config);
+    /// let params = HistoryParams {
+    ///     voice_id: Some("voice_id".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let mut items = history_client.history_stream(params);
+    /// while let Some(item) = items.try_next().await? {
+    ///     println!("{}: {:?}", item.history_item_id, item.text);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn history_stream(&self, params: HistoryParams) -> BoxStream<'static, Result<HistoryItem, Error>> {
+        let client = self.clone();
+
+        paginate(move |last_history_item_id: Option<String>| {
+            let client = client.clone();
+            let params = HistoryParams {
+                start_after_history_item_id: last_history_item_id
+                    .or_else(|| params.start_after_history_item_id.clone()),
+                ..params.clone()
+            };
+            async move {
+                let page = client.get_history(&params).await?;
+                let next = page.last_history_item_id.filter(|_| page.has_more);
+                Ok((page.history, next))
+            }
+        })
+    }
+
+    /// Fetches a single history item by its ID.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `HistoryItem`, or `Error` on failure.
+    pub async fn get_history_item(&self, history_item_id: &str) -> Result<HistoryItem, Error> {
+        let url = format!("{}/v1/history/{}", &self.config.api_url, history_item_id);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(response.json::<HistoryItem>().await?)
+    }
+
+    /// Downloads the audio of a history item.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the audio in the format of
+    /// `HistoryItem::content_type`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, history::HistoryClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let history_client = HistoryClient::new(config);
+    /// let audio = history_client.download_audio("history_item_id").await?;
+    /// std::fs::write("history_item.mp3", &audio)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_audio(&self, history_item_id: &str) -> Result<Bytes, Error> {
+        let response = self.send_audio_request(history_item_id).await?;
+        Ok(response.bytes().await?)
+    }
+
+    /// Downloads the audio of a history item as a stream of chunks, for writing it to disk
+    /// without holding it in memory.
+    pub async fn download_audio_stream(&self, history_item_id: &str) -> Result<AudioStream, Error> {
+        let response = self.send_audio_request(history_item_id).await?;
+        Ok(audio_stream(response))
+    }
+
+    async fn send_audio_request(&self, history_item_id: &str) -> Result<reqwest::Response, Error> {
+        let url = format!("{}/v1/history/{}/audio", &self.config.api_url, history_item_id);
+
+        send_with_retry(&self.config.retry_policy, true, || {
+            Ok(self.client.get(&url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await
+    }
+
+    /// Deletes a history item and its audio.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or error.
+    pub async fn delete_history_item(&self, history_item_id: &str) -> Result<(), Error> {
+        let url = format!("{}/v1/history/{}", &self.config.api_url, history_item_id);
+
+        send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::DELETE, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Downloads the audio of several history items at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `history_item_ids` - The items to download.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains a zip archive with one audio file per item, or
+    /// `Error` on failure. For a single item the API returns the audio file itself.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, history::HistoryClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let history_client = HistoryClient::new(config);
+    /// let ids = vec!["first_item_id".to_string(), "second_item_id".to_string()];
+    /// let archive = history_client.download_history_items(&ids).await?;
+    /// std::fs::write("history.zip", &archive)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_history_items(&self, history_item_ids: &[String]) -> Result<Bytes, Error> {
+        if history_item_ids.is_empty() {
+            return Err(Error::InvalidRequest("no history items to download".to_string()));
+        }
+        let url = format!("{}/v1/history/download", &self.config.api_url);
+        let request = DownloadRequest { history_item_ids };
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(self.client.post(&url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(&request))
+        })
+        .await?;
+
+        Ok(response.bytes().await?)
+    }
+
+    /// Mirrors the whole generation history into `dest_dir`.
+    ///
+    /// Every item is saved as `<history_item_id>.<ext>` with its audio, next to
+    /// `<history_item_id>.json` with its text, voice, model, settings, date and character cost.
+    /// Items already mirrored by an earlier run are skipped, and a run that was interrupted
+    /// resumes where it stopped. An item whose audio or metadata file has gone missing is
+    /// mirrored again. Files are written under a temporary name and renamed once
+    /// complete, so a partial file never looks mirrored.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest_dir` - The folder to mirror into. It is created if it doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains a `SyncReport`, or `Error` on failure. Items
+    /// mirrored before a failure stay recorded and are skipped by the next run.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, history::HistoryClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let history_client = HistoryClient::new(config);
+    /// let report = history_client.sync_history("archive/history").await?;
+    /// println!("{} downloaded, {} already mirrored", report.downloaded, report.skipped);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sync_history(&self, dest_dir: impl AsRef<Path>) -> Result<SyncReport, Error> {
+        let dest_dir = dest_dir.as_ref();
+        tokio::fs::create_dir_all(dest_dir).await?;
+
+        let mut log = SyncLog::open(&dest_dir.join(SYNC_STATE_FILE)).await?;
+
+        let mut report = SyncReport::default();
+        let mut items = self.history_stream(HistoryParams {
+            page_size: Some(100),
+            ..Default::default()
+        });
+
+        while let Some(item) = items.try_next().await? {
+            let target = SyncTarget::new(dest_dir, &item);
+            let action = target.action(&log, &item).await?;
+            if action == SyncAction::Skip {
+                report.skipped += 1;
+                continue;
+            }
+
+            if action == SyncAction::Download {
+                let temp_path = temp_path(&target.audio_path);
+                let mut file = tokio::fs::File::create(&temp_path).await?;
+                write_stream(self.download_audio_stream(&item.history_item_id).await?, &mut file).await?;
+                file.sync_all().await?;
+                drop(file);
+                tokio::fs::rename(&temp_path, &target.audio_path).await?;
+            }
+            target.write_sidecar(&item).await?;
+
+            log.record(&item.history_item_id).await?;
+            if action == SyncAction::Repair {
+                report.repaired += 1;
+            } else {
+                report.downloaded += 1;
+            }
+        }
+        log.file.sync_all().await?;
+
+        log_info(&format!(
+            "History sync to {}: {} downloaded, {} repaired, {} skipped",
+            dest_dir.display(),
+            report.downloaded,
+            report.repaired,
+            report.skipped
+        ));
+        Ok(report)
+    }
+}
+
+/// Returns the file extension for the MIME type of a history item's audio.
+fn audio_extension(content_type: Option<&str>) -> &'static str {
+    match content_type.map(|content_type| content_type.split(';').next().unwrap_or("").trim()) {
+        Some("audio/wav") | Some("audio/x-wav") | Some("audio/wave") => "wav",
+        Some("audio/ogg") | Some("audio/opus") => "ogg",
+        Some("audio/basic") | Some("audio/pcm") | Some("audio/L16") => "raw",
+        Some("audio/flac") => "flac",
+        _ => "mp3",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns an empty folder unique to `name`.
+    async fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("history-{}-{}", name, std::process::id()));
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        dir
+    }
+
+    fn item(history_item_id: &str) -> HistoryItem {
+        serde_json::from_value(serde_json::json!({
+            "history_item_id": history_item_id,
+            "date_unix": 1_700_000_000,
+            "text": "Hello",
+            "character_count_change_from": 100,
+            "character_count_change_to": 105,
+            "content_type": "audio/mpeg",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn truncated_last_line_is_dropped() {
+        let dir = temp_dir("truncated").await;
+        let path = dir.join(SYNC_STATE_FILE);
+        tokio::fs::write(&path, "first\nsecond\nthi").await.unwrap();
+
+        let mut log = SyncLog::open(&path).await.unwrap();
+        assert!(log.contains("first") && log.contains("second"));
+        assert!(!log.contains("thi"));
+
+        log.record("third").await.unwrap();
+        log.record("first").await.unwrap();
+        drop(log);
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "first\nsecond\nthird\n");
+        let log = SyncLog::open(&path).await.unwrap();
+        assert!(log.contains("third") && !log.contains("thi"));
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn logged_items_with_both_files_are_skipped() {
+        let dir = temp_dir("skip").await;
+        let mut log = SyncLog::open(&dir.join(SYNC_STATE_FILE)).await.unwrap();
+        let item = item("abc");
+        let target = SyncTarget::new(&dir, &item);
+        assert_eq!(target.audio_path, dir.join("abc.mp3"));
+
+        tokio::fs::write(&target.audio_path, b"audio").await.unwrap();
+        target.write_sidecar(&item).await.unwrap();
+        // Files left by a run that crashed before recording the item are not trusted.
+        assert_eq!(target.action(&log, &item).await.unwrap(), SyncAction::Download);
+
+        log.record("abc").await.unwrap();
+        assert_eq!(target.action(&log, &item).await.unwrap(), SyncAction::Skip);
+
+        tokio::fs::remove_file(&target.audio_path).await.unwrap();
+        assert_eq!(target.action(&log, &item).await.unwrap(), SyncAction::Download);
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn missing_sidecar_is_rewritten() {
+        let dir = temp_dir("repair").await;
+        let mut log = SyncLog::open(&dir.join(SYNC_STATE_FILE)).await.unwrap();
+        let item = item("abc");
+        let target = SyncTarget::new(&dir, &item);
+        tokio::fs::write(&target.audio_path, b"audio").await.unwrap();
+        log.record("abc").await.unwrap();
+
+        assert_eq!(target.action(&log, &item).await.unwrap(), SyncAction::Repair);
+        target.write_sidecar(&item).await.unwrap();
+        assert_eq!(target.action(&log, &item).await.unwrap(), SyncAction::Skip);
+
+        let sidecar: serde_json::Value =
+            serde_json::from_slice(&tokio::fs::read(&target.sidecar_path).await.unwrap()).unwrap();
+        assert_eq!(sidecar["history_item_id"], "abc");
+        assert_eq!(sidecar["character_count"], 5);
+        assert_eq!(sidecar["audio_file"], "abc.mp3");
+        assert_eq!(tokio::fs::read(&target.audio_path).await.unwrap(), b"audio");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
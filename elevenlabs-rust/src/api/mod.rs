@@ -2,8 +2,13 @@
 // This allows the rest of your crate to use `api::submodule` to access the contents of each submodule.
 
 // Declare each submodule here. Each submodule corresponds to a file with the same name.
+pub mod history;
+pub mod models;
+pub mod pvc;
+pub mod pronunciation_dictionaries;
 pub mod sts;
 pub mod tts;
+pub mod tts_websocket;
 pub mod user;
 pub mod voice_generation;
 pub mod voices;
@@ -0,0 +1,170 @@
+use std::sync::{Arc, Mutex};
+
+use reqwest::Client;
+
+use crate::api::sts::StsRequest;
+use crate::api::tts::TtsRequest;
+use crate::api::utils::{create_request, send_with_retry};
+use crate::config::Config;
+use crate::error::Error;
+use crate::models::Model;
+
+/// The model the API uses for Text-to-Speech requests that don't name one.
+pub const DEFAULT_TTS_MODEL_ID: &str = "eleven_multilingual_v2";
+
+/// The model the API uses for Speech-to-Speech requests that don't name one.
+pub const DEFAULT_STS_MODEL_ID: &str = "eleven_english_sts_v2";
+
+/// Client for interacting with the ElevenLabs Models API.
+///
+/// The model list is fetched once and cached, so the `check_*` methods can be called before
+/// every request without an extra round trip. Clones share the cache.
+///
+/// The checks are opt-in: enable them with
+/// [`TextToSpeechClient::with_model_checks`](crate::tts::TextToSpeechClient::with_model_checks)
+/// or [`SpeechToSpeechClient::with_model_checks`](crate::sts::SpeechToSpeechClient::with_model_checks)
+/// to have every request checked before it is sent.
+/// [`TextToSpeechClient::synthesize_long`](crate::tts::TextToSpeechClient::synthesize_long),
+/// which would otherwise fail after spending characters on earlier chunks, always checks its
+/// request up front.
+#[derive(Clone)]
+pub struct ModelsClient {
+    client: Client,
+    config: Arc<Config>,
+    cache: Arc<Mutex<Option<Arc<Vec<Model>>>>>,
+}
+
+impl ModelsClient {
+    /// Creates a new `ModelsClient` using the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A `Config` instance containing the necessary configuration.
+    pub fn new(config: Config) -> Self {
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `ModelsClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
+        ModelsClient {
+            client,
+            config,
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Fetches the models available to the account and refreshes the cache.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains every `Model`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, ModelsClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let models_client = ModelsClient::new(config);
+    /// for model in models_client.list_models().await? {
+    ///     println!("{}: {:?} characters per request", model.model_id, model.max_characters(true));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_models(&self) -> Result<Vec<Model>, Error> {
+        let url = format!("{}/v1/models", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        let models = response.json::<Vec<Model>>().await?;
+        *self.cache.lock().unwrap() = Some(Arc::new(models.clone()));
+        Ok(models)
+    }
+
+    /// Returns the model with the given ID, fetching the model list only if it isn't cached yet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `Model`, or `Error::InvalidRequest` if no
+    /// model has that ID.
+    pub async fn get_model(&self, model_id: &str) -> Result<Model, Error> {
+        let cached = self.cache.lock().unwrap().clone();
+        let models = match cached {
+            Some(models) => models,
+            None => Arc::new(self.list_models().await?),
+        };
+
+        models
+            .iter()
+            .find(|model| model.model_id == model_id)
+            .cloned()
+            .ok_or_else(|| Error::InvalidRequest(format!("unknown model {}", model_id)))
+    }
+
+    /// Checks that the model of `request` can serve it, before any characters are spent.
+    ///
+    /// The request is rejected if the model doesn't do Text-to-Speech, if the text is longer
+    /// than the model accepts on the account's tier, or if it uses a voice setting the model
+    /// doesn't support.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to check. Without a `model_id`, `DEFAULT_TTS_MODEL_ID` is checked.
+    /// * `subscribed` - Whether the account is on a paid tier, which allows longer requests.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `Model` the request will use, or
+    /// `Error::InvalidRequest` if the model can't serve it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, ElevenLabsClient, tts::TtsRequest};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = ElevenLabsClient::new(Config::new("your_api_key", "https://api.elevenlabs.io"));
+    /// let request = TtsRequest {
+    ///     text: "Hello, world!".to_string(),
+    ///     model_id: Some("eleven_flash_v2_5".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// client.models().check_tts_request(&request, true).await?;
+    /// let audio = client.tts().synthesize("voice_id", &request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn check_tts_request(&self, request: &TtsRequest, subscribed: bool) -> Result<Model, Error> {
+        let model_id = request.model_id.as_deref().unwrap_or(DEFAULT_TTS_MODEL_ID);
+        let model = self.get_model(model_id).await?;
+
+        model.check_text_to_speech(
+            request.text.chars().count(),
+            request.voice_settings.as_ref(),
+            subscribed,
+        )?;
+        Ok(model)
+    }
+
+    /// Checks that the model of `request` can serve a Speech-to-Speech conversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to check. Without a `model_id`, `DEFAULT_STS_MODEL_ID` is checked.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `Model` the request will use, or
+    /// `Error::InvalidRequest` if the model can't serve it.
+    pub async fn check_sts_request(&self, request: &StsRequest) -> Result<Model, Error> {
+        let model_id = request.model_id.as_deref().unwrap_or(DEFAULT_STS_MODEL_ID);
+        let model = self.get_model(model_id).await?;
+
+        model.check_voice_conversion(request.voice_settings.as_ref())?;
+        Ok(model)
+    }
+}
@@ -0,0 +1,492 @@
+use std::sync::Arc;
+
+use futures_util::stream::BoxStream;
+use reqwest::{multipart, Client};
+use serde::{Deserialize, Serialize};
+
+use crate::api::tts::PronunciationDictionaryLocator;
+use crate::api::utils::{create_request, paginate, send_with_retry, FileUpload, RetryPolicy};
+use crate::config::Config;
+use crate::error::Error;
+use crate::pls::{Lexicon, RuleDiff};
+
+/// The phonetic alphabet of a phoneme rule.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhonemeAlphabet {
+    /// The International Phonetic Alphabet.
+    #[serde(rename = "ipa")]
+    Ipa,
+    /// The CMU Arpabet.
+    #[serde(rename = "cmu-arpabet")]
+    CmuArpabet,
+}
+
+/// A rule of a pronunciation dictionary.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PronunciationRule {
+    /// Replaces `string_to_replace` with `alias` before it is spoken, e.g. "UN" with "United Nations".
+    Alias { string_to_replace: String, alias: String },
+    /// Speaks `string_to_replace` as `phoneme`. Only supported by some models.
+    Phoneme {
+        string_to_replace: String,
+        phoneme: String,
+        alphabet: PhonemeAlphabet,
+    },
+}
+
+impl PronunciationRule {
+    /// Creates an alias rule.
+    pub fn alias(string_to_replace: &str, alias: &str) -> Self {
+        PronunciationRule::Alias {
+            string_to_replace: string_to_replace.to_string(),
+            alias: alias.to_string(),
+        }
+    }
+
+    /// Creates a phoneme rule.
+    pub fn phoneme(string_to_replace: &str, phoneme: &str, alphabet: PhonemeAlphabet) -> Self {
+        PronunciationRule::Phoneme {
+            string_to_replace: string_to_replace.to_string(),
+            phoneme: phoneme.to_string(),
+            alphabet,
+        }
+    }
+
+    /// Returns the text the rule applies to, which also identifies it when removing rules.
+    pub fn string_to_replace(&self) -> &str {
+        match self {
+            PronunciationRule::Alias { string_to_replace, .. }
+            | PronunciationRule::Phoneme { string_to_replace, .. } => string_to_replace,
+        }
+    }
+}
+
+/// The metadata of a pronunciation dictionary.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PronunciationDictionary {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// The version created by the latest mutation.
+    pub latest_version_id: String,
+    pub latest_version_rules_num: Option<u32>,
+    pub created_by: Option<String>,
+    pub creation_time_unix: Option<i64>,
+    pub archived_time_unix: Option<i64>,
+}
+
+impl From<&PronunciationDictionary> for PronunciationDictionaryLocator {
+    fn from(dictionary: &PronunciationDictionary) -> Self {
+        PronunciationDictionaryLocator {
+            pronunciation_dictionary_id: dictionary.id.clone(),
+            version_id: dictionary.latest_version_id.clone(),
+        }
+    }
+}
+
+/// The version of a dictionary created by a mutation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DictionaryVersion {
+    /// The ID of the dictionary.
+    pub id: String,
+    pub version_id: String,
+    pub version_rules_num: Option<u32>,
+}
+
+impl From<&DictionaryVersion> for PronunciationDictionaryLocator {
+    fn from(version: &DictionaryVersion) -> Self {
+        PronunciationDictionaryLocator {
+            pronunciation_dictionary_id: version.id.clone(),
+            version_id: version.version_id.clone(),
+        }
+    }
+}
+
+impl From<DictionaryVersion> for PronunciationDictionaryLocator {
+    fn from(version: DictionaryVersion) -> Self {
+        PronunciationDictionaryLocator {
+            pronunciation_dictionary_id: version.id,
+            version_id: version.version_id,
+        }
+    }
+}
+
+/// The outcome of [`PronunciationDictionaryClient::sync_lexicon`].
+#[derive(Debug, Clone)]
+pub struct LexiconSync {
+    /// The rules that were added and removed. Empty if the dictionary was already up to date.
+    pub diff: RuleDiff,
+    /// The version of the dictionary after the sync.
+    pub version: DictionaryVersion,
+}
+
+/// Query parameters of the dictionary listing.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct DictionaryListParams {
+    /// The number of dictionaries per page, at most 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    /// The cursor of the page to fetch, from `DictionaryPage::next_cursor`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// A page of the dictionary listing.
+#[derive(Deserialize, Debug, Clone)]
+pub struct DictionaryPage {
+    pub pronunciation_dictionaries: Vec<PronunciationDictionary>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// The request payload for creating a dictionary from rules.
+#[derive(Serialize)]
+struct AddFromRulesRequest<'a> {
+    name: &'a str,
+    rules: &'a [PronunciationRule],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+/// The request payload for adding rules to a dictionary.
+#[derive(Serialize)]
+struct AddRulesRequest<'a> {
+    rules: &'a [PronunciationRule],
+}
+
+/// The request payload for removing rules from a dictionary.
+#[derive(Serialize)]
+struct RemoveRulesRequest<'a> {
+    rule_strings: &'a [String],
+}
+
+/// Client for interacting with the ElevenLabs Pronunciation Dictionaries API.
+#[derive(Clone)]
+pub struct PronunciationDictionaryClient {
+    client: Client,
+    config: Arc<Config>,
+}
+
+impl PronunciationDictionaryClient {
+    /// Creates a new `PronunciationDictionaryClient` using the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A `Config` instance containing the necessary configuration.
+    pub fn new(config: Config) -> Self {
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `PronunciationDictionaryClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
+        PronunciationDictionaryClient { client, config }
+    }
+
+    /// Creates a dictionary from a PLS lexicon file.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the dictionary.
+    /// * `file` - The PLS file: a file path, bytes in memory or an async reader.
+    /// * `description` - An optional description of the dictionary.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the first `DictionaryVersion`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, pronunciation_dictionaries::PronunciationDictionaryClient, tts::PronunciationDictionaryLocator, utils::FileUpload};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let dictionary_client = PronunciationDictionaryClient::new(config);
+    /// let version = dictionary_client
+    ///     .add_from_file("Brand names", FileUpload::from_path("brands.pls"), None)
+    ///     .await?;
+    /// let locator = PronunciationDictionaryLocator::from(version);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_from_file(
+        &self,
+        name: &str,
+        file: FileUpload,
+        description: Option<&str>,
+    ) -> Result<DictionaryVersion, Error> {
+        let url = format!("{}/v1/pronunciation-dictionaries/add-from-file", &self.config.api_url);
+        let file = file.prepare().await?;
+        let retry_policy = if file.is_replayable() {
+            self.config.retry_policy.clone()
+        } else {
+            RetryPolicy::none()
+        };
+
+        // Multipart bodies can't be cloned, so the form is rebuilt for every attempt.
+        let response = send_with_retry(&retry_policy, false, || {
+            let mut form = multipart::Form::new()
+                .text("name", name.to_string())
+                .part("file", file.to_part()?);
+            if let Some(description) = description {
+                form = form.text("description", description.to_string());
+            }
+            Ok(self.client.post(&url)
+                .header("xi-api-key", &self.config.api_key)
+                .multipart(form))
+        })
+        .await?;
+
+        Ok(response.json::<DictionaryVersion>().await?)
+    }
+
+    /// Creates a dictionary from a list of rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the dictionary.
+    /// * `rules` - The alias and phoneme rules of the dictionary.
+    /// * `description` - An optional description of the dictionary.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the first `DictionaryVersion`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, pronunciation_dictionaries::{PhonemeAlphabet, PronunciationDictionaryClient, PronunciationRule}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let dictionary_client = PronunciationDictionaryClient::new(config);
+    /// let rules = vec![
+    ///     PronunciationRule::alias("UN", "United Nations"),
+    ///     PronunciationRule::phoneme("tomato", "/tə'meɪtoʊ/", PhonemeAlphabet::Ipa),
+    /// ];
+    /// let version = dictionary_client.add_from_rules("Glossary", &rules, None).await?;
+    /// println!("Created {} at version {}", version.id, version.version_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_from_rules(
+        &self,
+        name: &str,
+        rules: &[PronunciationRule],
+        description: Option<&str>,
+    ) -> Result<DictionaryVersion, Error> {
+        let url = format!("{}/v1/pronunciation-dictionaries/add-from-rules", &self.config.api_url);
+        let request = AddFromRulesRequest { name, rules, description };
+
+        let response = send_with_retry(&self.config.retry_policy, false, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(&request))
+        })
+        .await?;
+
+        Ok(response.json::<DictionaryVersion>().await?)
+    }
+
+    /// Adds rules to a dictionary, replacing existing rules for the same strings.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the new `DictionaryVersion`, or `Error` on failure.
+    pub async fn add_rules(&self, dictionary_id: &str, rules: &[PronunciationRule]) -> Result<DictionaryVersion, Error> {
+        let url = format!("{}/v1/pronunciation-dictionaries/{}/add-rules", &self.config.api_url, dictionary_id);
+        let request = AddRulesRequest { rules };
+
+        let response = send_with_retry(&self.config.retry_policy, false, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(&request))
+        })
+        .await?;
+
+        Ok(response.json::<DictionaryVersion>().await?)
+    }
+
+    /// Removes the rules for the given strings from a dictionary.
+    ///
+    /// # Arguments
+    ///
+    /// * `dictionary_id` - The ID of the dictionary.
+    /// * `rule_strings` - The `string_to_replace` of every rule to remove.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the new `DictionaryVersion`, or `Error` on failure.
+    pub async fn remove_rules(&self, dictionary_id: &str, rule_strings: &[String]) -> Result<DictionaryVersion, Error> {
+        let url = format!("{}/v1/pronunciation-dictionaries/{}/remove-rules", &self.config.api_url, dictionary_id);
+        let request = RemoveRulesRequest { rule_strings };
+
+        let response = send_with_retry(&self.config.retry_policy, false, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(&request))
+        })
+        .await?;
+
+        Ok(response.json::<DictionaryVersion>().await?)
+    }
+
+    /// Fetches a single page of the dictionaries of the account.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `DictionaryPage`, or `Error` on failure.
+    pub async fn list_dictionaries(&self, params: &DictionaryListParams) -> Result<DictionaryPage, Error> {
+        let url = format!("{}/v1/pronunciation-dictionaries", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .query(params))
+        })
+        .await?;
+
+        Ok(response.json::<DictionaryPage>().await?)
+    }
+
+    /// Streams every dictionary of the account, fetching further pages as the stream is consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, pronunciation_dictionaries::{DictionaryListParams, PronunciationDictionaryClient}};
+    /// # use futures_util::TryStreamExt;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let dictionary_client = PronunciationDictionaryClient::new(config);
+    /// let mut dictionaries = dictionary_client.list_dictionaries_stream(DictionaryListParams::default());
+    /// while let Some(dictionary) = dictionaries.try_next().await? {
+    ///     println!("{}: {}", dictionary.id, dictionary.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_dictionaries_stream(
+        &self,
+        params: DictionaryListParams,
+    ) -> BoxStream<'static, Result<PronunciationDictionary, Error>> {
+        let client = self.clone();
+
+        paginate(move |cursor: Option<String>| {
+            let client = client.clone();
+            let params = DictionaryListParams {
+                cursor: cursor.or_else(|| params.cursor.clone()),
+                ..params.clone()
+            };
+            async move {
+                let page = client.list_dictionaries(&params).await?;
+                let next = page.next_cursor.filter(|_| page.has_more);
+                Ok((page.pronunciation_dictionaries, next))
+            }
+        })
+    }
+
+    /// Fetches the metadata of a dictionary, including its latest version.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `PronunciationDictionary`, or `Error` on failure.
+    pub async fn get_dictionary(&self, dictionary_id: &str) -> Result<PronunciationDictionary, Error> {
+        let url = format!("{}/v1/pronunciation-dictionaries/{}", &self.config.api_url, dictionary_id);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(response.json::<PronunciationDictionary>().await?)
+    }
+
+    /// Downloads a version of a dictionary as a PLS lexicon.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the PLS document, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, pronunciation_dictionaries::PronunciationDictionaryClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let dictionary_client = PronunciationDictionaryClient::new(config);
+    /// let dictionary = dictionary_client.get_dictionary("dictionary_id").await?;
+    /// let pls = dictionary_client.download_version(&dictionary.id, &dictionary.latest_version_id).await?;
+    /// std::fs::write("brands.pls", pls)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_version(&self, dictionary_id: &str, version_id: &str) -> Result<String, Error> {
+        let url = format!(
+            "{}/v1/pronunciation-dictionaries/{}/{}/download",
+            &self.config.api_url, dictionary_id, version_id
+        );
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(self.client.get(&url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(response.text().await?)
+    }
+
+    /// Brings a dictionary in line with a local lexicon, by comparing it with the latest
+    /// version of the dictionary and applying only the rules that were added, changed or
+    /// removed. Nothing is sent if the dictionary is already up to date.
+    ///
+    /// # Arguments
+    ///
+    /// * `dictionary_id` - The ID of the dictionary to update.
+    /// * `lexicon` - The lexicon the dictionary should match.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the applied `RuleDiff` and the resulting version,
+    /// or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, pls::Lexicon, pronunciation_dictionaries::PronunciationDictionaryClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let dictionary_client = PronunciationDictionaryClient::new(config);
+    /// let lexicon = Lexicon::parse(&std::fs::read_to_string("brands.pls")?)?;
+    /// let sync = dictionary_client.sync_lexicon("dictionary_id", &lexicon).await?;
+    /// println!(
+    ///     "{} rules added, {} removed, now at version {}",
+    ///     sync.diff.add.len(),
+    ///     sync.diff.remove.len(),
+    ///     sync.version.version_id
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sync_lexicon(&self, dictionary_id: &str, lexicon: &Lexicon) -> Result<LexiconSync, Error> {
+        let dictionary = self.get_dictionary(dictionary_id).await?;
+        let remote = Lexicon::parse(&self.download_version(dictionary_id, &dictionary.latest_version_id).await?)?;
+        let diff = RuleDiff::between(&remote.rules(), &lexicon.rules());
+
+        let mut version = DictionaryVersion {
+            id: dictionary.id,
+            version_id: dictionary.latest_version_id,
+            version_rules_num: dictionary.latest_version_rules_num,
+        };
+        // Adding replaces the rules for the same strings, so adding first means a failure
+        // between the two calls never leaves the dictionary without rules it had.
+        if !diff.add.is_empty() {
+            version = self.add_rules(dictionary_id, &diff.add).await?;
+        }
+        if !diff.remove.is_empty() {
+            version = self.remove_rules(dictionary_id, &diff.remove).await?;
+        }
+
+        Ok(LexiconSync { diff, version })
+    }
+}
@@ -0,0 +1,706 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::{multipart, Client};
+use serde::{Deserialize, Serialize};
+
+use crate::api::utils::files::write_atomically;
+use crate::api::utils::{create_request, log_info, send_with_retry, FileUpload, PreparedUpload, RetryPolicy};
+use crate::api::voices::{FineTuningState, Sample, SpeakerSeparation, SpeakerSeparationStatus, VoicesClient};
+use crate::config::Config;
+use crate::error::Error;
+
+/// The number of samples [`PvcWorkflow::upload_samples`] sends per request.
+pub const SAMPLE_BATCH_SIZE: usize = 5;
+
+/// The request payload for creating a professional voice.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct CreatePvcVoiceRequest {
+    pub name: String,
+    /// The language of the samples, e.g. `en`.
+    pub language: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<HashMap<String, String>>,
+}
+
+/// The training state of a professional voice for one model.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrainingProgress {
+    pub state: FineTuningState,
+    /// The progress of the training, from `0.0` to `1.0`.
+    pub progress: Option<f64>,
+    /// The latest status message, e.g. the reason of a failure.
+    pub message: Option<String>,
+}
+
+impl TrainingProgress {
+    /// Returns whether the training has ended, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, FineTuningState::FineTuned | FineTuningState::Failed)
+    }
+}
+
+#[derive(Deserialize)]
+struct CreatePvcVoiceResponse {
+    voice_id: String,
+}
+
+#[derive(Serialize)]
+struct SelectSpeakersRequest<'a> {
+    selected_speaker_ids: &'a [String],
+}
+
+#[derive(Serialize)]
+struct TrainRequest<'a> {
+    model_id: &'a str,
+}
+
+/// Client for the ElevenLabs Professional Voice Cloning API.
+///
+/// For the whole lifecycle of a voice, from creation to training, see [`PvcWorkflow`].
+#[derive(Clone)]
+pub struct PvcClient {
+    client: Client,
+    config: Arc<Config>,
+}
+
+impl PvcClient {
+    /// Creates a new `PvcClient` using the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A `Config` instance containing the necessary configuration.
+    pub fn new(config: Config) -> Self {
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `PvcClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
+        PvcClient { client, config }
+    }
+
+    fn voices(&self) -> VoicesClient {
+        VoicesClient::from_parts(self.client.clone(), self.config.clone())
+    }
+
+    /// Creates a professional voice without samples.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the ID of the new voice, or `Error` on failure.
+    pub async fn create_voice(&self, request: &CreatePvcVoiceRequest) -> Result<String, Error> {
+        let url = format!("{}/v1/voices/pvc", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, false, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(request))
+        })
+        .await?;
+
+        Ok(response.json::<CreatePvcVoiceResponse>().await?.voice_id)
+    }
+
+    /// Uploads training samples to a professional voice.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice.
+    /// * `files` - The samples: file paths, bytes in memory or async readers.
+    /// * `remove_background_noise` - Removes background noise from the samples.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the uploaded `Sample`s, or `Error` on failure.
+    pub async fn add_samples(
+        &self,
+        voice_id: &str,
+        files: Vec<FileUpload>,
+        remove_background_noise: bool,
+    ) -> Result<Vec<Sample>, Error> {
+        let files = prepare_all(files).await?;
+        self.add_prepared_samples(voice_id, files, remove_background_noise).await
+    }
+
+    /// Chooses the speakers of a separated sample whose audio is used for training.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or error.
+    pub async fn select_speakers(&self, voice_id: &str, sample_id: &str, speaker_ids: &[String]) -> Result<(), Error> {
+        let url = format!("{}/v1/voices/pvc/{}/samples/{}", &self.config.api_url, voice_id, sample_id);
+        let request = SelectSpeakersRequest {
+            selected_speaker_ids: speaker_ids,
+        };
+
+        send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(&request))
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Submits the documents proving the right to clone the voice, for manual verification.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice.
+    /// * `files` - The verification documents, e.g. a signed consent form.
+    /// * `extra_text` - Optional context for the reviewers.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or error.
+    pub async fn submit_verification(
+        &self,
+        voice_id: &str,
+        files: Vec<FileUpload>,
+        extra_text: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!("{}/v1/voices/pvc/{}/verification", &self.config.api_url, voice_id);
+        let files = prepare_all(files).await?;
+
+        send_with_retry(&retry_policy_for(&self.config, &files), false, || {
+            let mut form = multipart::Form::new();
+            for file in &files {
+                form = form.part("files", file.to_part()?);
+            }
+            if let Some(extra_text) = extra_text {
+                form = form.text("extra_text", extra_text.to_string());
+            }
+            Ok(self.client.post(&url)
+                .header("xi-api-key", &self.config.api_key)
+                .multipart(form))
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Starts training a professional voice for a model.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or error.
+    pub async fn start_training(&self, voice_id: &str, model_id: &str) -> Result<(), Error> {
+        let url = format!("{}/v1/voices/pvc/{}/train", &self.config.api_url, voice_id);
+        let request = TrainRequest { model_id };
+
+        send_with_retry(&self.config.retry_policy, false, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(&request))
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches the training state of a professional voice for a model.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `TrainingProgress`, or `Error` on failure.
+    pub async fn get_training_progress(&self, voice_id: &str, model_id: &str) -> Result<TrainingProgress, Error> {
+        let voice = self.voices().get_voice_metadata(voice_id, false).await?;
+        let fine_tuning = voice.fine_tuning.unwrap_or_default();
+
+        Ok(TrainingProgress {
+            state: fine_tuning
+                .state
+                .get(model_id)
+                .copied()
+                .unwrap_or(FineTuningState::NotStarted),
+            progress: fine_tuning.progress.get(model_id).copied(),
+            message: fine_tuning.message.get(model_id).cloned(),
+        })
+    }
+
+    async fn add_prepared_samples(
+        &self,
+        voice_id: &str,
+        files: Vec<PreparedUpload>,
+        remove_background_noise: bool,
+    ) -> Result<Vec<Sample>, Error> {
+        let url = format!("{}/v1/voices/pvc/{}/samples", &self.config.api_url, voice_id);
+
+        let response = send_with_retry(&retry_policy_for(&self.config, &files), false, || {
+            let mut form = multipart::Form::new()
+                .text("remove_background_noise", remove_background_noise.to_string());
+            for file in &files {
+                form = form.part("files", file.to_part()?);
+            }
+            Ok(self.client.post(&url)
+                .header("xi-api-key", &self.config.api_key)
+                .multipart(form))
+        })
+        .await?;
+
+        Ok(response.json::<Vec<Sample>>().await?)
+    }
+}
+
+/// A sample uploaded by a [`PvcWorkflow`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PvcSample {
+    pub sample_id: String,
+    pub file_name: Option<String>,
+    /// The size of the uploaded file in bytes.
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    /// The state of the speaker separation, if it was started.
+    pub separation: Option<SpeakerSeparationStatus>,
+    #[serde(default)]
+    pub selected_speaker_ids: Vec<String>,
+}
+
+/// How far a [`PvcWorkflow`] has got.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PvcStage {
+    /// The voice has not been created yet.
+    New,
+    /// The voice exists but has no samples.
+    Created,
+    SamplesUploaded,
+    VerificationSubmitted,
+    Training,
+    Trained,
+    Failed,
+}
+
+/// The professional cloning of one voice, from creation to training.
+///
+/// Every step records its outcome in the workflow and skips work that was already done, so
+/// a workflow can be saved after each step, loaded again after a restart and resumed by
+/// calling the same steps again.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use elevenlabs_rust::{Config, ElevenLabsClient, pvc::PvcWorkflow, utils::FileUpload};
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = ElevenLabsClient::new(Config::new("your_api_key", "https://api.elevenlabs.io"));
+/// let pvc = client.pvc();
+/// let state_file = "narrator.pvc.json";
+/// let mut workflow = match PvcWorkflow::load(state_file).await {
+///     Ok(workflow) => workflow,
+///     Err(_) => PvcWorkflow::new("Narrator", "en", "eleven_multilingual_v2"),
+/// };
+///
+/// workflow.create(&pvc).await?;
+/// workflow.save(state_file).await?;
+///
+/// let samples = (1..=12).map(|n| FileUpload::from_path(format!("sessions/take_{:02}.wav", n))).collect();
+/// workflow.upload_samples(&pvc, samples, false).await?;
+/// workflow.save(state_file).await?;
+///
+/// workflow.submit_verification(&pvc, vec![FileUpload::from_path("consent.pdf")], None).await?;
+/// workflow.start_training(&pvc).await?;
+/// workflow.save(state_file).await?;
+///
+/// let progress = workflow
+///     .wait_for_training(&pvc, Duration::from_secs(60), Duration::from_secs(6 * 60 * 60))
+///     .await?;
+/// workflow.save(state_file).await?;
+/// println!("Training is in state {:?}", progress.state);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PvcWorkflow {
+    pub name: String,
+    /// The language of the samples, e.g. `en`.
+    pub language: String,
+    pub description: Option<String>,
+    /// The model the voice is trained for.
+    pub model_id: String,
+    /// Set once the voice is created.
+    pub voice_id: Option<String>,
+    #[serde(default)]
+    pub samples: Vec<PvcSample>,
+    #[serde(default)]
+    pub verification_submitted: bool,
+    #[serde(default)]
+    pub training_started: bool,
+    /// The training state seen by the latest poll.
+    pub training: Option<TrainingProgress>,
+}
+
+impl PvcWorkflow {
+    /// Starts a workflow for a new voice.
+    pub fn new(name: &str, language: &str, model_id: &str) -> Self {
+        PvcWorkflow {
+            name: name.to_string(),
+            language: language.to_string(),
+            description: None,
+            model_id: model_id.to_string(),
+            voice_id: None,
+            samples: Vec::new(),
+            verification_submitted: false,
+            training_started: false,
+            training: None,
+        }
+    }
+
+    /// Loads a workflow saved with [`save`](Self::save).
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let contents = tokio::fs::read(path).await?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+
+    /// Saves the workflow as JSON. The file is replaced atomically, so a crash never leaves
+    /// a partial state behind.
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        write_atomically(path.as_ref(), &serde_json::to_vec_pretty(self)?).await
+    }
+
+    /// Returns how far the workflow has got.
+    pub fn stage(&self) -> PvcStage {
+        match &self.training {
+            Some(training) if training.state == FineTuningState::FineTuned => return PvcStage::Trained,
+            Some(training) if training.state == FineTuningState::Failed => return PvcStage::Failed,
+            _ => {}
+        }
+
+        if self.training_started {
+            PvcStage::Training
+        } else if self.verification_submitted {
+            PvcStage::VerificationSubmitted
+        } else if !self.samples.is_empty() {
+            PvcStage::SamplesUploaded
+        } else if self.voice_id.is_some() {
+            PvcStage::Created
+        } else {
+            PvcStage::New
+        }
+    }
+
+    /// Returns the ID of the voice, or `Error::InvalidRequest` if it hasn't been created yet.
+    pub fn voice_id(&self) -> Result<&str, Error> {
+        self.voice_id
+            .as_deref()
+            .ok_or_else(|| Error::InvalidRequest("the voice has not been created yet".to_string()))
+    }
+
+    /// Creates the voice, unless it already exists.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the ID of the voice, or `Error` on failure.
+    pub async fn create(&mut self, client: &PvcClient) -> Result<&str, Error> {
+        if self.voice_id.is_none() {
+            let request = CreatePvcVoiceRequest {
+                name: self.name.clone(),
+                language: self.language.clone(),
+                description: self.description.clone(),
+                labels: None,
+            };
+            self.voice_id = Some(client.create_voice(&request).await?);
+        }
+        self.voice_id()
+    }
+
+    /// Uploads samples in batches of [`SAMPLE_BATCH_SIZE`], skipping files whose name and size
+    /// match a sample uploaded earlier. Files are read from disk one batch at a time, and the
+    /// workflow is updated after every batch, so the uploads of a failed call are kept.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the number of samples uploaded by this call,
+    /// or `Error` on failure.
+    pub async fn upload_samples(
+        &mut self,
+        client: &PvcClient,
+        files: Vec<FileUpload>,
+        remove_background_noise: bool,
+    ) -> Result<usize, Error> {
+        let voice_id = self.voice_id()?.to_string();
+        let mut pending = Vec::new();
+        for file in files {
+            let size = file.size().await?;
+            if !self.has_sample(&file.file_name(), size) {
+                pending.push((file, size));
+            }
+        }
+
+        let mut uploaded = 0;
+        while !pending.is_empty() {
+            let (batch, sizes): (Vec<FileUpload>, Vec<u64>) =
+                pending.drain(..pending.len().min(SAMPLE_BATCH_SIZE)).unzip();
+            let samples = client
+                .add_prepared_samples(&voice_id, prepare_all(batch).await?, remove_background_noise)
+                .await?;
+            uploaded += samples.len();
+            // The API returns one sample per file, in the order they were sent. The local size is
+            // recorded so later calls can match files against it without reading them.
+            self.samples.extend(samples.into_iter().zip(sizes).map(|(sample, size)| PvcSample {
+                sample_id: sample.sample_id,
+                file_name: sample.file_name,
+                size_bytes: Some(size),
+                separation: None,
+                selected_speaker_ids: Vec::new(),
+            }));
+        }
+
+        log_info(&format!("Uploaded {} samples to voice {}", uploaded, voice_id));
+        Ok(uploaded)
+    }
+
+    /// Whether a file with this name and size was uploaded earlier.
+    fn has_sample(&self, file_name: &str, size_bytes: u64) -> bool {
+        self.samples
+            .iter()
+            .any(|sample| sample.file_name.as_deref() == Some(file_name) && sample.size_bytes == Some(size_bytes))
+    }
+
+    /// Starts the speaker separation of the given samples, e.g. recordings with several
+    /// speakers, unless it was already started.
+    pub async fn separate_speakers(&mut self, client: &PvcClient, sample_ids: &[String]) -> Result<(), Error> {
+        let voice_id = self.voice_id()?.to_string();
+        let voices = client.voices();
+
+        for sample in self.samples.iter_mut().filter(|sample| sample_ids.contains(&sample.sample_id)) {
+            if sample.separation.is_none() {
+                voices.start_speaker_separation(&voice_id, &sample.sample_id).await?;
+                sample.separation = Some(SpeakerSeparationStatus::Pending);
+            }
+        }
+        Ok(())
+    }
+
+    /// Refreshes the status of every pending speaker separation.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the separations that have completed, so their
+    /// speakers can be chosen with [`select_speakers`](Self::select_speakers), or `Error` on failure.
+    pub async fn poll_speaker_separation(&mut self, client: &PvcClient) -> Result<Vec<SpeakerSeparation>, Error> {
+        let voice_id = self.voice_id()?.to_string();
+        let voices = client.voices();
+        let mut completed = Vec::new();
+
+        for sample in &mut self.samples {
+            if sample.separation != Some(SpeakerSeparationStatus::Pending) {
+                continue;
+            }
+            let separation = voices.get_speaker_separation(&voice_id, &sample.sample_id).await?;
+            sample.separation = Some(separation.status);
+            sample.selected_speaker_ids = separation.selected_speaker_ids.clone();
+            if separation.status == SpeakerSeparationStatus::Completed {
+                completed.push(separation);
+            }
+        }
+        Ok(completed)
+    }
+
+    /// Chooses the speakers of a separated sample whose audio is used for training.
+    pub async fn select_speakers(&mut self, client: &PvcClient, sample_id: &str, speaker_ids: &[String]) -> Result<(), Error> {
+        let voice_id = self.voice_id()?.to_string();
+        client.select_speakers(&voice_id, sample_id, speaker_ids).await?;
+
+        if let Some(sample) = self.samples.iter_mut().find(|sample| sample.sample_id == sample_id) {
+            sample.selected_speaker_ids = speaker_ids.to_vec();
+        }
+        Ok(())
+    }
+
+    /// Submits the verification documents, unless they were already submitted.
+    pub async fn submit_verification(
+        &mut self,
+        client: &PvcClient,
+        files: Vec<FileUpload>,
+        extra_text: Option<&str>,
+    ) -> Result<(), Error> {
+        if !self.verification_submitted {
+            client.submit_verification(self.voice_id()?, files, extra_text).await?;
+            self.verification_submitted = true;
+        }
+        Ok(())
+    }
+
+    /// Starts the training, unless it was already started.
+    ///
+    /// A training that failed is started again: the recorded failure is cleared, so the
+    /// workflow goes back to [`PvcStage::Training`].
+    pub async fn start_training(&mut self, client: &PvcClient) -> Result<(), Error> {
+        if self.stage() == PvcStage::Failed {
+            self.training_started = false;
+        }
+        if !self.training_started {
+            client.start_training(self.voice_id()?, &self.model_id).await?;
+            self.training_started = true;
+            self.training = None;
+        }
+        Ok(())
+    }
+
+    /// Fetches the training state and records it in the workflow.
+    pub async fn poll_training(&mut self, client: &PvcClient) -> Result<TrainingProgress, Error> {
+        let progress = client.get_training_progress(self.voice_id()?, &self.model_id).await?;
+        self.training = Some(progress.clone());
+        Ok(progress)
+    }
+
+    /// Polls the training state every `interval` until the training has ended, or until
+    /// `timeout` has elapsed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the last `TrainingProgress` polled, or `Error` on
+    /// failure. Check [`TrainingProgress::is_finished`] to tell whether the wait timed out.
+    pub async fn wait_for_training(
+        &mut self,
+        client: &PvcClient,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<TrainingProgress, Error> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let progress = self.poll_training(client).await?;
+            if progress.is_finished() || tokio::time::Instant::now() + interval > deadline {
+                return Ok(progress);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+async fn prepare_all(files: Vec<FileUpload>) -> Result<Vec<PreparedUpload>, Error> {
+    let mut prepared = Vec::with_capacity(files.len());
+    for file in files {
+        prepared.push(file.prepare().await?);
+    }
+    Ok(prepared)
+}
+
+/// Disables retries when an upload can only be sent once.
+fn retry_policy_for(config: &Config, files: &[PreparedUpload]) -> RetryPolicy {
+    if files.iter().all(PreparedUpload::is_replayable) {
+        config.retry_policy.clone()
+    } else {
+        RetryPolicy::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(sample_id: &str, file_name: &str, size_bytes: u64) -> PvcSample {
+        PvcSample {
+            sample_id: sample_id.to_string(),
+            file_name: Some(file_name.to_string()),
+            size_bytes: Some(size_bytes),
+            separation: None,
+            selected_speaker_ids: Vec::new(),
+        }
+    }
+
+    fn training(state: FineTuningState) -> Option<TrainingProgress> {
+        Some(TrainingProgress {
+            state,
+            progress: None,
+            message: None,
+        })
+    }
+
+    #[test]
+    fn stage_follows_the_recorded_steps() {
+        let mut workflow = PvcWorkflow::new("Narrator", "en", "eleven_multilingual_v2");
+        assert_eq!(workflow.stage(), PvcStage::New);
+        workflow.voice_id = Some("voice".to_string());
+        assert_eq!(workflow.stage(), PvcStage::Created);
+        workflow.samples.push(sample("s1", "take_01.wav", 1000));
+        assert_eq!(workflow.stage(), PvcStage::SamplesUploaded);
+        workflow.verification_submitted = true;
+        assert_eq!(workflow.stage(), PvcStage::VerificationSubmitted);
+        workflow.training_started = true;
+        assert_eq!(workflow.stage(), PvcStage::Training);
+
+        for state in [
+            FineTuningState::NotStarted,
+            FineTuningState::Queued,
+            FineTuningState::FineTuning,
+            FineTuningState::Delayed,
+            FineTuningState::Unknown,
+        ] {
+            workflow.training = training(state);
+            assert_eq!(workflow.stage(), PvcStage::Training, "{:?}", state);
+        }
+        workflow.training = training(FineTuningState::FineTuned);
+        assert_eq!(workflow.stage(), PvcStage::Trained);
+        workflow.training = training(FineTuningState::Failed);
+        assert_eq!(workflow.stage(), PvcStage::Failed);
+
+        // The outcome of a training wins over the steps recorded before it.
+        workflow.training_started = false;
+        assert_eq!(workflow.stage(), PvcStage::Failed);
+        workflow.training = None;
+        assert_eq!(workflow.stage(), PvcStage::VerificationSubmitted);
+        workflow.verification_submitted = false;
+        workflow.training_started = true;
+        assert_eq!(workflow.stage(), PvcStage::Training);
+    }
+
+    #[tokio::test]
+    async fn partial_workflow_round_trips() {
+        let mut workflow = PvcWorkflow::new("Narrator", "en", "eleven_multilingual_v2");
+        workflow.voice_id = Some("voice".to_string());
+        workflow.samples.push(sample("s1", "take_01.wav", 1000));
+        workflow.samples[0].separation = Some(SpeakerSeparationStatus::Completed);
+        workflow.samples[0].selected_speaker_ids = vec!["speaker_0".to_string()];
+        workflow.verification_submitted = true;
+
+        let path = std::env::temp_dir().join(format!("pvc-workflow-{}.json", std::process::id()));
+        workflow.save(&path).await.unwrap();
+        let loaded = PvcWorkflow::load(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(loaded.stage(), PvcStage::VerificationSubmitted);
+        assert_eq!(loaded.voice_id.as_deref(), Some("voice"));
+        assert_eq!(loaded.samples[0].size_bytes, Some(1000));
+        assert_eq!(loaded.samples[0].separation, Some(SpeakerSeparationStatus::Completed));
+        assert_eq!(loaded.samples[0].selected_speaker_ids, ["speaker_0"]);
+        assert!(!loaded.training_started);
+    }
+
+    #[test]
+    fn workflows_saved_before_sample_sizes_load() {
+        let json = r#"{
+            "name": "Narrator",
+            "language": "en",
+            "description": null,
+            "model_id": "eleven_multilingual_v2",
+            "voice_id": "voice",
+            "samples": [{ "sample_id": "s1", "file_name": "take_01.wav", "separation": null }],
+            "training": null
+        }"#;
+
+        let workflow: PvcWorkflow = serde_json::from_str(json).unwrap();
+        assert_eq!(workflow.stage(), PvcStage::SamplesUploaded);
+        assert_eq!(workflow.samples[0].size_bytes, None);
+        assert!(workflow.samples[0].selected_speaker_ids.is_empty());
+        assert!(!workflow.verification_submitted);
+        // Without a recorded size the file can't be matched, so it is uploaded again.
+        assert!(!workflow.has_sample("take_01.wav", 1000));
+    }
+
+    #[test]
+    fn samples_match_on_name_and_size() {
+        let mut workflow = PvcWorkflow::new("Narrator", "en", "eleven_multilingual_v2");
+        workflow.samples.push(sample("s1", "take.wav", 1000));
+
+        assert!(workflow.has_sample("take.wav", 1000));
+        assert!(!workflow.has_sample("take.wav", 999));
+        assert!(!workflow.has_sample("other.wav", 1000));
+    }
+}
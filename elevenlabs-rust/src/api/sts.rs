@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use reqwest::{multipart, Client, RequestBuilder};
+
+use crate::api::models::ModelsClient;
+use crate::api::utils::{audio_stream, send_with_retry, AudioStream, FileUpload, PreparedUpload, RetryPolicy};
+use crate::config::Config;
+use crate::error::{parse_request_id, Error};
+use crate::models::{Audio, OutputFormat, VoiceSettings};
+
+/// The parameters of a Speech-to-Speech conversion.
+#[derive(Debug, Default, Clone)]
+pub struct StsRequest {
+    /// The model to convert with, e.g. `eleven_multilingual_sts_v2`.
+    pub model_id: Option<String>,
+    /// Overrides the stored settings of the target voice.
+    pub voice_settings: Option<VoiceSettings>,
+    /// Makes the conversion deterministic, on a best-effort basis.
+    pub seed: Option<u32>,
+    /// Removes background noise from the source audio before converting it.
+    pub remove_background_noise: Option<bool>,
+    /// The format of the returned audio. Defaults to `Mp3_44100_128`.
+    pub output_format: Option<OutputFormat>,
+    /// Trades quality for latency, from `0` (no optimization) to `4` (maximum).
+    pub optimize_streaming_latency: Option<u8>,
+}
+
+/// Client for interacting with the ElevenLabs Speech-to-Speech API.
+#[derive(Clone)]
+pub struct SpeechToSpeechClient {
+    client: Client,
+    config: Arc<Config>,
+    /// Looks up the model of a request for model checks.
+    models: ModelsClient,
+    check_models: bool,
+}
+
+impl SpeechToSpeechClient {
+    /// Creates a new `SpeechToSpeechClient` using the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A `Config` instance containing the necessary configuration.
+    pub fn new(config: Config) -> Self {
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `SpeechToSpeechClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
+        SpeechToSpeechClient {
+            models: ModelsClient::from_parts(client.clone(), config.clone()),
+            client,
+            config,
+            check_models: false,
+        }
+    }
+
+    /// Makes model checks share the model cache of `models`.
+    pub(crate) fn with_models(mut self, models: ModelsClient) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// Checks every request against its model before sending it, as in
+    /// [`ModelsClient::check_sts_request`], so a request the model can't serve is refused
+    /// before the audio is uploaded. The model list is fetched once and cached.
+    pub fn with_model_checks(mut self) -> Self {
+        self.check_models = true;
+        self
+    }
+
+    /// Re-voices recorded speech with the given voice, keeping its timing and delivery.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice to convert the speech to.
+    /// * `audio` - The source recording: a file path, bytes in memory or an async reader.
+    /// * `request` - The `StsRequest` containing the model and other conversion parameters.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the converted speech as `Audio`,
+    /// or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, sts::{SpeechToSpeechClient, StsRequest}, utils::FileUpload};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let sts_client = SpeechToSpeechClient::new(config);
+    /// let request = StsRequest {
+    ///     model_id: Some("eleven_multilingual_sts_v2".to_string()),
+    ///     remove_background_noise: Some(true),
+    ///     ..Default::default()
+    /// };
+    /// let audio = sts_client.convert("voice_id", FileUpload::from_path("line_042.wav"), &request).await?;
+    /// std::fs::write("line_042.mp3", &audio.data)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert(&self, voice_id: &str, audio: FileUpload, request: &StsRequest) -> Result<Audio, Error> {
+        let url = format!("{}/v1/speech-to-speech/{}", &self.config.api_url, voice_id);
+        let format = request.output_format.unwrap_or_default();
+
+        let response = self.send(&url, audio, request).await?;
+        let request_id = parse_request_id(response.headers());
+
+        Ok(Audio {
+            data: response.bytes().await?,
+            format,
+            request_id,
+        })
+    }
+
+    /// Re-voices recorded speech and streams the converted audio back while it is being generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice to convert the speech to.
+    /// * `audio` - The source recording: a file path, bytes in memory or an async reader.
+    /// * `request` - The `StsRequest` containing the model and other conversion parameters.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains an `AudioStream` yielding audio chunks as they
+    /// arrive, or `Error` on failure.
+    pub async fn convert_stream(
+        &self,
+        voice_id: &str,
+        audio: FileUpload,
+        request: &StsRequest,
+    ) -> Result<AudioStream, Error> {
+        let url = format!("{}/v1/speech-to-speech/{}/stream", &self.config.api_url, voice_id);
+
+        let response = self.send(&url, audio, request).await?;
+        Ok(audio_stream(response))
+    }
+
+    /// Uploads `audio` to `url`, rebuilding the multipart form for every attempt.
+    async fn send(&self, url: &str, audio: FileUpload, request: &StsRequest) -> Result<reqwest::Response, Error> {
+        if let Some(voice_settings) = &request.voice_settings {
+            voice_settings.validate()?;
+        }
+        if self.check_models {
+            self.models.check_sts_request(request).await?;
+        }
+        // The cost of a conversion depends on the length of the audio, so it can only be
+        // refused once the budget is exhausted and charged after the fact.
+        self.config.check_budget()?;
+
+        let audio = audio.prepare().await?;
+        let retry_policy = if audio.is_replayable() {
+            self.config.retry_policy.clone()
+        } else {
+            RetryPolicy::none()
+        };
+
+        let response = send_with_retry(&retry_policy, false, || self.build_request(url, &audio, request)).await?;
+        self.config.record_budget(response.headers());
+        Ok(response)
+    }
+
+    fn build_request(&self, url: &str, audio: &PreparedUpload, request: &StsRequest) -> Result<RequestBuilder, Error> {
+        let format = request.output_format.unwrap_or_default();
+        let mut form = multipart::Form::new().part("audio", audio.to_part()?);
+
+        if let Some(model_id) = &request.model_id {
+            form = form.text("model_id", model_id.clone());
+        }
+        if let Some(voice_settings) = &request.voice_settings {
+            form = form.text("voice_settings", serde_json::to_string(voice_settings)?);
+        }
+        if let Some(seed) = request.seed {
+            form = form.text("seed", seed.to_string());
+        }
+        if let Some(remove_background_noise) = request.remove_background_noise {
+            form = form.text("remove_background_noise", remove_background_noise.to_string());
+        }
+
+        let mut query = vec![("output_format", format.as_str().to_string())];
+        if let Some(latency) = request.optimize_streaming_latency {
+            query.push(("optimize_streaming_latency", latency.to_string()));
+        }
+
+        Ok(self.client.post(url)
+            .header("Accept", format.mime_type())
+            .header("xi-api-key", &self.config.api_key)
+            .query(&query)
+            .multipart(form))
+    }
+}
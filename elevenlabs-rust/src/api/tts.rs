@@ -1,41 +1,166 @@
-use reqwest::{Client, header};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::stream::{BoxStream, StreamExt};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 
+use crate::api::models::{ModelsClient, DEFAULT_TTS_MODEL_ID};
+use crate::api::tts_websocket::{StreamInputOptions, StreamInputSession};
+use crate::api::utils::{audio_stream, json_lines, send_with_retry, split_text, AudioStream};
+use crate::api::voices::VoicesClient;
 use crate::config::Config;
-// use crate::utils::{UtilsError};
-
-/// Settings for customizing the voice output.
-#[derive(Serialize, Deserialize, Debug)]
-pub struct VoiceSettings {
-    pub stability: i32,
-    pub similarity_boost: i32,
-    pub style: Option<f32>,
-    pub use_speaker_boost: Option<bool>,
-    // Include additional settings as per the API.
-}
+use crate::error::{parse_request_id, Error};
+use crate::models::{Audio, OutputFormat};
+
+pub use crate::models::VoiceSettings;
 
 /// A list of pronunciation dictionary locators to be applied to the text.
 /// They will be applied in order. Up to 3 locators per request.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PronunciationDictionaryLocator {
     pub pronunciation_dictionary_id: String,
     pub version_id: String,
 }
 
 /// The request payload for the TTS API.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct TtsRequest {
     pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub model_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub voice_settings: Option<VoiceSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pronunciation_dictionary_locators: Option<Vec<PronunciationDictionaryLocator>>,
+    /// The text that comes before `text`, used to keep the prosody continuous across requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_text: Option<String>,
+    /// The text that comes after `text`, used to keep the prosody continuous across requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_text: Option<String>,
+    /// The IDs of up to 3 requests that generated the audio preceding this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_request_ids: Option<Vec<String>>,
+    /// The IDs of up to 3 requests that generated the audio following this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_request_ids: Option<Vec<String>>,
+    /// The format of the returned audio, sent as the `output_format` query parameter.
+    /// Defaults to `Mp3_44100_128`.
+    #[serde(skip)]
+    pub output_format: Option<OutputFormat>,
+}
+
+impl TtsRequest {
+    /// Checks the request for values the API would reject.
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(voice_settings) = &self.voice_settings {
+            voice_settings.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// The chunk size of long-form synthesis with a model the API reports no character limit for.
+const UNREPORTED_MAX_CHARACTERS: usize = 5_000;
+
+/// The number of previous request IDs the API accepts per request.
+const MAX_PREVIOUS_REQUEST_IDS: usize = 3;
+
+/// Options of [`TextToSpeechClient::synthesize_long`].
+#[derive(Debug, Default, Clone)]
+pub struct LongFormOptions {
+    /// The maximum number of characters per request. Defaults to the limit of the model.
+    pub max_characters_per_request: Option<usize>,
+    /// Whether the account is on a paid tier, whose models accept longer requests. Defaults
+    /// to `false`, the limit of the free tier.
+    pub subscribed: bool,
+}
+
+/// The result of a long-form synthesis.
+#[derive(Debug, Clone)]
+pub struct LongFormAudio {
+    /// The audio of every chunk, joined in order.
+    pub audio: Audio,
+    /// The ID of the request that generated each chunk, in order.
+    pub request_ids: Vec<String>,
+}
+
+/// Query options accepted by the streaming Text-to-Speech endpoint.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct StreamOptions {
+    /// Trades quality for latency, from `0` (no optimization) to `4` (maximum).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimize_streaming_latency: Option<u8>,
+    /// The audio format of the stream. Overrides `TtsRequest::output_format` when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+}
+
+/// The timing of each character of the synthesized text.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Alignment {
+    pub characters: Vec<String>,
+    pub character_start_times_seconds: Vec<f64>,
+    pub character_end_times_seconds: Vec<f64>,
+}
+
+/// Synthesized audio together with the timing of the characters it speaks.
+#[derive(Debug, Clone)]
+pub struct TimestampedAudio {
+    pub audio: Audio,
+    /// The timing of the characters of the original text.
+    pub alignment: Option<Alignment>,
+    /// The timing of the characters of the text after normalization (e.g. numbers spelled out).
+    pub normalized_alignment: Option<Alignment>,
+}
+
+/// A stream of timestamped audio chunks, yielded as soon as the API produces them.
+pub type TimestampedAudioStream = BoxStream<'static, Result<TimestampedAudio, Error>>;
+
+/// The response body of the timestamp endpoints, and each line of their streaming variant.
+#[derive(Deserialize)]
+struct TimestampedAudioResponse {
+    audio_base64: String,
+    alignment: Option<Alignment>,
+    normalized_alignment: Option<Alignment>,
+}
+
+impl TimestampedAudioResponse {
+    /// Decodes the audio, which is encoded in `format`.
+    fn decode(self, format: OutputFormat, request_id: Option<String>) -> Result<TimestampedAudio, Error> {
+        let data = BASE64
+            .decode(self.audio_base64)
+            .map_err(|err| Error::Protocol(format!("invalid audio: {}", err)))?;
+
+        Ok(TimestampedAudio {
+            audio: Audio {
+                data: Bytes::from(data),
+                format,
+                request_id,
+            },
+            alignment: self.alignment,
+            normalized_alignment: self.normalized_alignment,
+        })
+    }
 }
 
 /// Client for interacting with the ElevenLabs Text-to-Speech API.
+#[derive(Clone)]
 pub struct TextToSpeechClient {
     client: Client,
-    config: Config,
+    config: Arc<Config>,
+    /// Looks up the model of a request for long-form synthesis and model checks.
+    models: ModelsClient,
+    /// The tier requests are checked for against their model, if model checks are enabled.
+    model_checks: Option<bool>,
+    use_stored_voice_settings: bool,
+    /// The stored settings of every voice looked up so far, shared by clones.
+    stored_voice_settings: Arc<Mutex<HashMap<String, VoiceSettings>>>,
 }
 
 impl TextToSpeechClient {
@@ -45,12 +170,108 @@ impl TextToSpeechClient {
     ///
     /// * `config` - A `Config` instance containing the necessary configuration.
     pub fn new(config: Config) -> Self {
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `TextToSpeechClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
         TextToSpeechClient {
-            client: Client::new(),
+            models: ModelsClient::from_parts(client.clone(), config.clone()),
+            client,
             config,
+            model_checks: None,
+            use_stored_voice_settings: false,
+            stored_voice_settings: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Makes long-form synthesis and model checks share the model cache of `models`.
+    pub(crate) fn with_models(mut self, models: ModelsClient) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// Checks every request against its model before sending it, as in
+    /// [`ModelsClient::check_tts_request`], so a request the model can't serve is refused
+    /// before any characters are spent. The model list is fetched once and cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscribed` - Whether the account is on a paid tier, which allows longer requests.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, ElevenLabsClient, tts::TtsRequest};
+    /// # async fn run(request: TtsRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = ElevenLabsClient::new(Config::new("your_api_key", "https://api.elevenlabs.io"));
+    /// let tts_client = client.tts().with_model_checks(true);
+    /// let audio = tts_client.synthesize("voice_id", &request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_model_checks(mut self, subscribed: bool) -> Self {
+        self.model_checks = Some(subscribed);
+        self
+    }
+
+    /// Synthesizes requests without `voice_settings` with the settings stored for the voice,
+    /// instead of leaving the choice to the API.
+    ///
+    /// The settings of each voice are looked up on first use and then pinned: they never
+    /// expire, so renders keep sounding the same even if the voice is edited later. To pick up
+    /// changes, call [`refresh_stored_voice_settings`](Self::refresh_stored_voice_settings)
+    /// for one voice, or [`invalidate_stored_voice_settings`](Self::invalidate_stored_voice_settings)
+    /// and [`clear_stored_voice_settings`](Self::clear_stored_voice_settings) to look settings
+    /// up again on next use.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, ElevenLabsClient, tts::TtsRequest};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = ElevenLabsClient::new(Config::new("your_api_key", "https://api.elevenlabs.io"));
+    /// let tts_client = client.tts().with_stored_voice_settings();
+    /// let request = TtsRequest {
+    ///     text: "Hello, world!".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let audio = tts_client.synthesize("voice_id", &request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_stored_voice_settings(mut self) -> Self {
+        self.use_stored_voice_settings = true;
+        self
+    }
+
+    /// Fetches the stored settings of a voice now and pins them in place of the cached ones.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the new `VoiceSettings`, or `Error` on failure, in
+    /// which case the cached settings are kept.
+    pub async fn refresh_stored_voice_settings(&self, voice_id: &str) -> Result<VoiceSettings, Error> {
+        let settings = VoicesClient::from_parts(self.client.clone(), self.config.clone())
+            .get_voice_settings(voice_id)
+            .await?;
+        self.stored_voice_settings
+            .lock()
+            .unwrap()
+            .insert(voice_id.to_string(), settings.clone());
+        Ok(settings)
+    }
+
+    /// Forgets the cached settings of a voice, so they are looked up again on next use.
+    pub fn invalidate_stored_voice_settings(&self, voice_id: &str) {
+        self.stored_voice_settings.lock().unwrap().remove(voice_id);
+    }
+
+    /// Forgets the cached settings of every voice, so they are looked up again on next use.
+    pub fn clear_stored_voice_settings(&self) {
+        self.stored_voice_settings.lock().unwrap().clear();
+    }
+
     /// Converts text to speech using the specified voice and settings.
     ///
     /// # Arguments
@@ -60,49 +281,416 @@ impl TextToSpeechClient {
     ///
     /// # Returns
     ///
-    /// A `Result` which, on success, contains the synthesized speech as a byte array,
-    /// or `UtilsError` on failure.
+    /// A `Result` which, on success, contains the synthesized speech as `Audio` in the
+    /// requested output format, or `Error` on failure.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, models::OutputFormat, tts::{TextToSpeechClient, TtsRequest, VoiceSettings}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
     /// let tts_client = TextToSpeechClient::new(config);
     /// let request = TtsRequest {
     ///     text: "Hello, world!".to_string(),
     ///     model_id: Some("default_model_id".to_string()),
-    ///     voice_settings: Some(VoiceSettings {
-    ///         stability: Some(0.5),
-    ///         similarity_boost: Some(0.5),
-    ///         // Other settings...
-    ///     }),
-    ///     // Other fields...
+    ///     voice_settings: Some(VoiceSettings::new(0.5, 0.75)?),
+    ///     output_format: Some(OutputFormat::Pcm16000),
+    ///     ..Default::default()
     /// };
-    /// let response = tts_client.synthesize("voice_id", &request).await.unwrap();
+    /// let audio = tts_client.synthesize("voice_id", &request).await?;
+    /// println!("{} Hz, {} channel(s)", audio.sample_rate(), audio.channels());
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn synthesize(&self, voice_id: &str, request: &TtsRequest) -> Result<Vec<u8>, UtilsError> {
+    pub async fn synthesize(&self, voice_id: &str, request: &TtsRequest) -> Result<Audio, Error> {
+        let request = self.resolve_voice_settings(voice_id, request).await?;
+        request.validate()?;
+        self.check_model(&request).await?;
+        self.render(voice_id, &request).await
+    }
+
+    /// Sends a request whose voice settings were resolved and which was checked.
+    async fn render(&self, voice_id: &str, request: &TtsRequest) -> Result<Audio, Error> {
         let url = format!("{}/v1/text-to-speech/{}", &self.config.api_url, voice_id);
+        let format = request.output_format.unwrap_or_default();
 
-        let mut req_headers = header::HeaderMap::new();
-        req_headers.insert("Accept", header::HeaderValue::from_static("audio/mpeg"));
-        req_headers.insert("Content-Type", header::HeaderValue::from_static("application/json"));
-        req_headers.insert("xi-api-key", header::HeaderValue::from_str(&self.config.api_key)?);
+        let response = self.send_charged(&request.text, || {
+            Ok(self.client.post(&url)
+                .header("Accept", format.mime_type())
+                .header("xi-api-key", &self.config.api_key)
+                .query(&[("output_format", format.as_str())])
+                .json(&request))
+        })
+        .await?;
 
-        let response = self.client.post(url)
-            .headers(req_headers)
-            .json(&request)
-            .send()
-            .await
-            .map_err(UtilsError::Http)?;
+        let request_id = parse_request_id(response.headers());
 
-        match response.error_for_status_ref() {
-            Ok(_) => {
-                let bytes = response.bytes().await.map_err(UtilsError::Http)?;
-                Ok(bytes.to_vec())
-            },
-            Err(e) => {
-                let error_msg = format!("🚨 Failed to synthesize text: {}", e);
-                Err(UtilsError::Custom(error_msg))
+        Ok(Audio {
+            data: response.bytes().await?,
+            format,
+            request_id,
+        })
+    }
+
+    /// Converts text of any length to speech.
+    ///
+    /// The text is split at paragraph and sentence boundaries into chunks that fit the
+    /// model's character limit, which is fetched from the Models API once and cached. Before
+    /// the first chunk is sent, the request is checked against the model as in
+    /// [`ModelsClient::check_tts_request`], so a request the model can't serve fails before
+    /// any characters are spent. Each chunk is synthesized with the surrounding text and the
+    /// IDs of the preceding requests, so the voice stays continuous across chunks, and the
+    /// audio of all chunks is joined according to the output format.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice model to use for synthesis.
+    /// * `request` - The `TtsRequest` containing the full text and other parameters for synthesis.
+    /// * `options` - The tier of the account, or a chunk size overriding the model's limit.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the joined audio and the IDs of the requests
+    /// that generated it, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, tts::{LongFormOptions, TextToSpeechClient, TtsRequest}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let tts_client = TextToSpeechClient::new(config);
+    /// let request = TtsRequest {
+    ///     text: std::fs::read_to_string("chapter_1.txt")?,
+    ///     model_id: Some("eleven_multilingual_v2".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let result = tts_client.synthesize_long("voice_id", &request, &LongFormOptions::default()).await?;
+    /// std::fs::write("chapter_1.mp3", &result.audio.data)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn synthesize_long(
+        &self,
+        voice_id: &str,
+        request: &TtsRequest,
+        options: &LongFormOptions,
+    ) -> Result<LongFormAudio, Error> {
+        let request = self.resolve_voice_settings(voice_id, request).await?;
+        request.validate()?;
+        let model_id = request.model_id.as_deref().unwrap_or(DEFAULT_TTS_MODEL_ID);
+        let model = self.models.get_model(model_id).await?;
+        let max_characters = options
+            .max_characters_per_request
+            .or_else(|| model.max_characters(options.subscribed))
+            .unwrap_or(UNREPORTED_MAX_CHARACTERS);
+        let chunks = split_text(&request.text, max_characters);
+        let longest_chunk = chunks.iter().map(|chunk| chunk.chars().count()).max().unwrap_or_default();
+        model.check_text_to_speech(longest_chunk, request.voice_settings.as_ref(), options.subscribed)?;
+
+        if chunks.is_empty() {
+            return Err(Error::InvalidRequest("text is empty".to_string()));
+        }
+
+        let mut clips = Vec::with_capacity(chunks.len());
+        let mut request_ids: Vec<String> = Vec::with_capacity(chunks.len());
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            let previous_request_ids = request_ids
+                [request_ids.len().saturating_sub(MAX_PREVIOUS_REQUEST_IDS)..]
+                .to_vec();
+            let chunk_request = TtsRequest {
+                text: chunk.clone(),
+                previous_text: index.checked_sub(1).map(|previous| chunks[previous].clone()),
+                next_text: chunks.get(index + 1).cloned(),
+                previous_request_ids: Some(previous_request_ids).filter(|ids| !ids.is_empty()),
+                next_request_ids: None,
+                ..request.as_ref().clone()
+            };
+
+            // The request was checked for the tier in `options` above, so the chunks are
+            // rendered without the client's model checks.
+            let audio = self.render(voice_id, &chunk_request).await?;
+            if let Some(request_id) = &audio.request_id {
+                request_ids.push(request_id.clone());
             }
+            clips.push(audio);
         }
+
+        Ok(LongFormAudio {
+            audio: Audio::concat(clips)?,
+            request_ids,
+        })
+    }
+
+    /// Converts text to speech and streams the audio back while it is being generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice model to use for synthesis.
+    /// * `request` - The `TtsRequest` containing the text and other parameters for synthesis.
+    /// * `options` - The latency and format options of the stream.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains an `AudioStream` yielding audio chunks as they
+    /// arrive, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, tts::{StreamOptions, TextToSpeechClient, TtsRequest}};
+    /// # async fn run(request: TtsRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let tts_client = TextToSpeechClient::new(config);
+    /// let options = StreamOptions {
+    ///     optimize_streaming_latency: Some(3),
+    ///     ..Default::default()
+    /// };
+    /// let stream = tts_client.synthesize_stream("voice_id", &request, &options).await?;
+    /// let mut stdout = tokio::io::stdout();
+    /// elevenlabs_rust::utils::write_stream(stream, &mut stdout).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn synthesize_stream(
+        &self,
+        voice_id: &str,
+        request: &TtsRequest,
+        options: &StreamOptions,
+    ) -> Result<AudioStream, Error> {
+        let request = self.resolve_voice_settings(voice_id, request).await?;
+        request.validate()?;
+        self.check_model(&request).await?;
+        let url = format!("{}/v1/text-to-speech/{}/stream", &self.config.api_url, voice_id);
+        let options = StreamOptions {
+            output_format: options.output_format.or(request.output_format),
+            ..options.clone()
+        };
+
+        let response = self.send_charged(&request.text, || {
+            Ok(self.client.post(&url)
+                .header("Accept", options.output_format.unwrap_or_default().mime_type())
+                .header("xi-api-key", &self.config.api_key)
+                .query(&options)
+                .json(&request))
+        })
+        .await?;
+
+        Ok(audio_stream(response))
+    }
+
+    /// Converts text to speech and returns the audio together with the timing of every character.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice model to use for synthesis.
+    /// * `request` - The `TtsRequest` containing the text and other parameters for synthesis.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the decoded audio and its `Alignment`,
+    /// or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, tts::{TextToSpeechClient, TtsRequest}};
+    /// # async fn run(request: TtsRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let tts_client = TextToSpeechClient::new(config);
+    /// let result = tts_client.synthesize_with_timestamps("voice_id", &request).await?;
+    /// if let Some(alignment) = result.alignment {
+    ///     for (character, start) in alignment.characters.iter().zip(&alignment.character_start_times_seconds) {
+    ///         println!("{} at {:.2}s", character, start);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn synthesize_with_timestamps(&self, voice_id: &str, request: &TtsRequest) -> Result<TimestampedAudio, Error> {
+        let request = self.resolve_voice_settings(voice_id, request).await?;
+        request.validate()?;
+        self.check_model(&request).await?;
+        let url = format!("{}/v1/text-to-speech/{}/with-timestamps", &self.config.api_url, voice_id);
+        let format = request.output_format.unwrap_or_default();
+
+        let response = self.send_charged(&request.text, || {
+            Ok(self.client.post(&url)
+                .header("Accept", "application/json")
+                .header("xi-api-key", &self.config.api_key)
+                .query(&[("output_format", format.as_str())])
+                .json(&request))
+        })
+        .await?;
+
+        let request_id = parse_request_id(response.headers());
+        response.json::<TimestampedAudioResponse>().await?.decode(format, request_id)
+    }
+
+    /// Converts text to speech and streams the audio back in chunks, each with the timing of
+    /// the characters it speaks.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice model to use for synthesis.
+    /// * `request` - The `TtsRequest` containing the text and other parameters for synthesis.
+    /// * `options` - The latency and format options of the stream.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains a stream of `TimestampedAudio` chunks,
+    /// or `Error` on failure.
+    pub async fn synthesize_stream_with_timestamps(
+        &self,
+        voice_id: &str,
+        request: &TtsRequest,
+        options: &StreamOptions,
+    ) -> Result<TimestampedAudioStream, Error> {
+        let request = self.resolve_voice_settings(voice_id, request).await?;
+        request.validate()?;
+        self.check_model(&request).await?;
+        let url = format!("{}/v1/text-to-speech/{}/stream/with-timestamps", &self.config.api_url, voice_id);
+        let options = StreamOptions {
+            output_format: options.output_format.or(request.output_format),
+            ..options.clone()
+        };
+
+        let response = self.send_charged(&request.text, || {
+            Ok(self.client.post(&url)
+                .header("Accept", "application/json")
+                .header("xi-api-key", &self.config.api_key)
+                .query(&options)
+                .json(&request))
+        })
+        .await?;
+
+        let format = options.output_format.unwrap_or_default();
+        let request_id = parse_request_id(response.headers());
+        Ok(json_lines::<TimestampedAudioResponse>(response)
+            .map(move |line| line.and_then(|line| line.decode(format, request_id.clone())))
+            .boxed())
+    }
+
+    /// Opens a WebSocket session that converts text to speech while the text is still being
+    /// produced, via the `/stream-input` endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice model to use for synthesis.
+    /// * `options` - The model, format and initial message of the session.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the open `StreamInputSession`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, tts::TextToSpeechClient};
+    /// # use elevenlabs_rust::tts_websocket::{GenerationConfig, StreamInputOptions};
+    /// # use futures_util::StreamExt;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let tts_client = TextToSpeechClient::new(config);
+    /// let options = StreamInputOptions {
+    ///     generation_config: Some(GenerationConfig { chunk_length_schedule: vec![120, 160, 250, 290] }),
+    ///     ..Default::default()
+    /// };
+    /// let session = tts_client.stream_input("voice_id", &options).await?;
+    /// let (mut sender, mut receiver) = session.split();
+    ///
+    /// for token in ["Hello ", "from ", "a ", "language ", "model. "] {
+    ///     sender.send_text(token).await?;
+    /// }
+    /// sender.close().await?;
+    ///
+    /// while let Some(chunk) = receiver.next().await {
+    ///     println!("Received {} bytes of audio", chunk?.audio.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stream_input(&self, voice_id: &str, options: &StreamInputOptions) -> Result<StreamInputSession, Error> {
+        if self.use_stored_voice_settings && options.voice_settings.is_none() {
+            let options = StreamInputOptions {
+                voice_settings: Some(self.stored_voice_settings(voice_id).await?),
+                ..options.clone()
+            };
+            return StreamInputSession::connect(&self.config, voice_id, &options).await;
+        }
+        StreamInputSession::connect(&self.config, voice_id, options).await
+    }
+
+    /// Fills in the stored settings of the voice if the request has none and
+    /// `with_stored_voice_settings` is enabled.
+    async fn resolve_voice_settings<'a>(
+        &self,
+        voice_id: &str,
+        request: &'a TtsRequest,
+    ) -> Result<Cow<'a, TtsRequest>, Error> {
+        if !self.use_stored_voice_settings || request.voice_settings.is_some() {
+            return Ok(Cow::Borrowed(request));
+        }
+
+        Ok(Cow::Owned(TtsRequest {
+            voice_settings: Some(self.stored_voice_settings(voice_id).await?),
+            ..request.clone()
+        }))
+    }
+
+    /// Checks `request` against its model, if model checks are enabled.
+    async fn check_model(&self, request: &TtsRequest) -> Result<(), Error> {
+        if let Some(subscribed) = self.model_checks {
+            self.models.check_tts_request(request, subscribed).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the stored settings of a voice, looking them up on first use.
+    async fn stored_voice_settings(&self, voice_id: &str) -> Result<VoiceSettings, Error> {
+        if let Some(settings) = self.stored_voice_settings.lock().unwrap().get(voice_id) {
+            return Ok(settings.clone());
+        }
+        self.refresh_stored_voice_settings(voice_id).await
+    }
+
+    /// Charges `text` to the budget and sends the request built by `build_request`, refunding
+    /// the charge if it fails.
+    ///
+    /// A request that timed out may still have been processed and billed, so only failures
+    /// the API never acted on are retried.
+    async fn send_charged<F>(&self, text: &str, build_request: F) -> Result<Response, Error>
+    where
+        F: FnMut() -> Result<RequestBuilder, Error>,
+    {
+        let charge = self.config.charge_budget(text)?;
+        send_with_retry(&self.config.retry_policy, false, build_request)
+            .await
+            .inspect_err(|_| self.config.refund_budget(charge))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamped_lines_decode_audio_and_alignment() {
+        let line = r#"{"audio_base64":"SGk=","alignment":{"characters":["H","i"],"character_start_times_seconds":[0.0,0.12],"character_end_times_seconds":[0.12,0.2]},"normalized_alignment":null}"#;
+
+        let response: TimestampedAudioResponse = serde_json::from_str(line).unwrap();
+        let result = response.decode(OutputFormat::Pcm16000, Some("req".to_string())).unwrap();
+        assert_eq!(result.audio.data, Bytes::from_static(b"Hi"));
+        assert_eq!(result.audio.format, OutputFormat::Pcm16000);
+        assert_eq!(result.audio.request_id.as_deref(), Some("req"));
+        let alignment = result.alignment.unwrap();
+        assert_eq!(alignment.characters, ["H", "i"]);
+        assert_eq!(alignment.character_end_times_seconds, [0.12, 0.2]);
+        assert!(result.normalized_alignment.is_none());
+
+        let response: TimestampedAudioResponse = serde_json::from_str(r#"{"audio_base64":"not base64!"}"#).unwrap();
+        assert!(matches!(response.decode(OutputFormat::default(), None), Err(Error::Protocol(_))));
     }
 }
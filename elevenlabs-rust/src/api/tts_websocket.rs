@@ -0,0 +1,477 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::stream::{self, BoxStream, SplitSink, Stream, StreamExt};
+use futures_util::SinkExt;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::api::tts::PronunciationDictionaryLocator;
+use crate::config::Config;
+use crate::error::Error;
+use crate::models::{OutputFormat, VoiceSettings};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// The write half of a session, shared so the receiver can close it after the final chunk.
+type Sink = Arc<Mutex<SplitSink<Socket, Message>>>;
+
+/// Controls when the server starts generating audio from the buffered text.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GenerationConfig {
+    /// The number of buffered characters that triggers each successive generation,
+    /// e.g. `[120, 160, 250, 290]`. Each value must be between 50 and 500.
+    pub chunk_length_schedule: Vec<u32>,
+}
+
+/// Options used to open a `StreamInputSession`.
+#[derive(Debug, Default, Clone)]
+pub struct StreamInputOptions {
+    pub model_id: Option<String>,
+    /// The audio format of the returned chunks. Defaults to `Mp3_44100_128`.
+    pub output_format: Option<OutputFormat>,
+    /// Trades quality for latency, from `0` (no optimization) to `4` (maximum).
+    pub optimize_streaming_latency: Option<u8>,
+    /// Seconds without new text after which the server closes the connection.
+    pub inactivity_timeout: Option<u32>,
+    /// Sent with the initial message.
+    pub voice_settings: Option<VoiceSettings>,
+    /// Sent with the initial message.
+    pub generation_config: Option<GenerationConfig>,
+    /// Sent with the initial message.
+    pub pronunciation_dictionary_locators: Option<Vec<PronunciationDictionaryLocator>>,
+}
+
+/// The first ("beginning of stream") message of a session.
+#[derive(Serialize)]
+struct InitialMessage<'a> {
+    text: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    voice_settings: Option<&'a VoiceSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generation_config: Option<&'a GenerationConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pronunciation_dictionary_locators: Option<&'a Vec<PronunciationDictionaryLocator>>,
+}
+
+/// A chunk of text sent during a session.
+#[derive(Serialize)]
+struct TextMessage<'a> {
+    text: &'a str,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    try_trigger_generation: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    flush: bool,
+}
+
+/// Character timing information attached to a chunk of streamed audio.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamInputAlignment {
+    pub chars: Vec<String>,
+    pub char_start_times_ms: Vec<u32>,
+    #[serde(rename = "charsDurationsMs")]
+    pub char_durations_ms: Vec<u32>,
+}
+
+/// A message received from the server.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerMessage {
+    audio: Option<String>,
+    is_final: Option<bool>,
+    alignment: Option<StreamInputAlignment>,
+    normalized_alignment: Option<StreamInputAlignment>,
+    error: Option<String>,
+    message: Option<String>,
+}
+
+/// A decoded chunk of audio produced during a session.
+#[derive(Debug, Clone)]
+pub struct StreamInputChunk {
+    /// The audio of this chunk. Empty for the final message.
+    pub audio: Bytes,
+    /// Whether this is the last message of the session.
+    pub is_final: bool,
+    /// The timing of the characters spoken in this chunk.
+    pub alignment: Option<StreamInputAlignment>,
+    /// The timing of the normalized characters spoken in this chunk.
+    pub normalized_alignment: Option<StreamInputAlignment>,
+}
+
+/// A WebSocket session that converts text to speech while the text is still being produced,
+/// e.g. token by token from a language model.
+///
+/// Text is pushed with [`send_text`](Self::send_text) and audio is read by polling the session
+/// as a `Stream` of [`StreamInputChunk`]s. To send and receive concurrently, [`split`](Self::split)
+/// the session into its two halves.
+pub struct StreamInputSession {
+    sender: StreamInputSender,
+    receiver: StreamInputReceiver,
+}
+
+impl StreamInputSession {
+    /// Opens a session for `voice_id` and sends the initial message.
+    pub(crate) async fn connect(config: &Config, voice_id: &str, options: &StreamInputOptions) -> Result<Self, Error> {
+        if let Some(voice_settings) = &options.voice_settings {
+            voice_settings.validate()?;
+        }
+        let url = stream_input_url(config, voice_id, options)?;
+        let mut request = url.as_str().into_client_request()?;
+        request.headers_mut().insert(
+            "xi-api-key",
+            HeaderValue::from_str(&config.api_key).map_err(|err| Error::Protocol(err.to_string()))?,
+        );
+
+        let (socket, _) = tokio_tungstenite::connect_async(request).await?;
+        let (sink, read) = socket.split();
+        let sink = Arc::new(Mutex::new(sink));
+
+        let mut sender = StreamInputSender {
+            sink: sink.clone(),
+            config: config.clone(),
+        };
+        let initial = InitialMessage {
+            text: " ",
+            voice_settings: options.voice_settings.as_ref(),
+            generation_config: options.generation_config.as_ref(),
+            pronunciation_dictionary_locators: options.pronunciation_dictionary_locators.as_ref(),
+        };
+        sender.send_json(&initial).await?;
+
+        let chunks = stream::unfold(Some((read, sink)), |state| async move {
+            let (mut read, sink) = state?;
+            loop {
+                match read.next().await? {
+                    Ok(Message::Text(text)) => {
+                        return match decode_message(&text) {
+                            Ok(chunk) if chunk.is_final => {
+                                // Nothing follows the final chunk, so close the connection
+                                // rather than waiting for the server to do it. The sink may
+                                // already be closed, which is fine.
+                                let _ = sink.lock().await.close().await;
+                                Some((Ok(chunk), None))
+                            }
+                            Ok(chunk) => Some((Ok(chunk), Some((read, sink)))),
+                            Err(err) => Some((Err(err), None)),
+                        };
+                    }
+                    Ok(Message::Close(_)) => return None,
+                    Ok(_) => continue,
+                    Err(err) => return Some((Err(err.into()), None)),
+                }
+            }
+        })
+        .boxed();
+
+        Ok(StreamInputSession {
+            sender,
+            receiver: StreamInputReceiver { chunks },
+        })
+    }
+
+    /// Sends a chunk of text. See [`StreamInputSender::send_text`].
+    pub async fn send_text(&mut self, text: &str) -> Result<(), Error> {
+        self.sender.send_text(text).await
+    }
+
+    /// Sends a chunk of text and asks the server to start generating immediately.
+    /// See [`StreamInputSender::send_text_and_trigger`].
+    pub async fn send_text_and_trigger(&mut self, text: &str) -> Result<(), Error> {
+        self.sender.send_text_and_trigger(text).await
+    }
+
+    /// Forces generation of all buffered text. See [`StreamInputSender::flush`].
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        self.sender.flush().await
+    }
+
+    /// Sends the end-of-stream message. See [`StreamInputSender::close`].
+    pub async fn close(&mut self) -> Result<(), Error> {
+        self.sender.close().await
+    }
+
+    /// Splits the session into a sender and a receiver that can be used from different tasks.
+    pub fn split(self) -> (StreamInputSender, StreamInputReceiver) {
+        (self.sender, self.receiver)
+    }
+}
+
+impl Stream for StreamInputSession {
+    type Item = Result<StreamInputChunk, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+/// The sending half of a `StreamInputSession`.
+pub struct StreamInputSender {
+    sink: Sink,
+    config: Config,
+}
+
+impl StreamInputSender {
+    /// Sends a chunk of text. The server buffers text until the next threshold of the
+    /// chunk length schedule is reached. Chunks should end with a space.
+    pub async fn send_text(&mut self, text: &str) -> Result<(), Error> {
+        self.send_charged(text, false).await
+    }
+
+    /// Sends a chunk of text and asks the server to start generating as soon as possible.
+    pub async fn send_text_and_trigger(&mut self, text: &str) -> Result<(), Error> {
+        self.send_charged(text, true).await
+    }
+
+    /// Forces the server to generate audio for all buffered text, e.g. at the end of a turn,
+    /// without closing the session.
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        self.send_message(" ", false, true).await
+    }
+
+    /// Sends the end-of-stream message. The server generates the remaining audio and sends the
+    /// final chunk, upon which the receiver closes the connection.
+    pub async fn close(&mut self) -> Result<(), Error> {
+        self.send_message("", false, false).await
+    }
+
+    /// Charges `text` to the budget before sending it, and refunds it if it could not be sent.
+    async fn send_charged(&mut self, text: &str, try_trigger_generation: bool) -> Result<(), Error> {
+        let charge = self.config.charge_budget(text)?;
+        let result = self.send_message(text, try_trigger_generation, false).await;
+        if result.is_err() {
+            self.config.refund_budget(charge);
+        }
+        result
+    }
+
+    async fn send_message(&mut self, text: &str, try_trigger_generation: bool, flush: bool) -> Result<(), Error> {
+        self.send_json(&TextMessage {
+            text,
+            try_trigger_generation,
+            flush,
+        })
+        .await
+    }
+
+    async fn send_json<T: Serialize>(&mut self, message: &T) -> Result<(), Error> {
+        let message = serde_json::to_string(message)?;
+        self.sink.lock().await.send(Message::Text(message)).await?;
+        Ok(())
+    }
+}
+
+/// The receiving half of a `StreamInputSession`, yielding audio chunks until the final one.
+pub struct StreamInputReceiver {
+    chunks: BoxStream<'static, Result<StreamInputChunk, Error>>,
+}
+
+impl Stream for StreamInputReceiver {
+    type Item = Result<StreamInputChunk, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.chunks.poll_next_unpin(cx)
+    }
+}
+
+/// Builds the `ws(s)://` URL of the stream-input endpoint from the configured API URL.
+fn stream_input_url(config: &Config, voice_id: &str, options: &StreamInputOptions) -> Result<Url, Error> {
+    let base = if let Some(rest) = config.api_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = config.api_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        config.api_url.clone()
+    };
+
+    let mut url = Url::parse(&format!("{}/v1/text-to-speech/{}/stream-input", base, voice_id))
+        .map_err(|err| Error::Protocol(err.to_string()))?;
+    {
+        let mut query = url.query_pairs_mut();
+        if let Some(model_id) = &options.model_id {
+            query.append_pair("model_id", model_id);
+        }
+        if let Some(output_format) = &options.output_format {
+            query.append_pair("output_format", output_format.as_str());
+        }
+        if let Some(latency) = options.optimize_streaming_latency {
+            query.append_pair("optimize_streaming_latency", &latency.to_string());
+        }
+        if let Some(timeout) = options.inactivity_timeout {
+            query.append_pair("inactivity_timeout", &timeout.to_string());
+        }
+    }
+    if url.query() == Some("") {
+        url.set_query(None);
+    }
+
+    Ok(url)
+}
+
+fn decode_message(text: &str) -> Result<StreamInputChunk, Error> {
+    let message: ServerMessage = serde_json::from_str(text)?;
+
+    if let Some(error) = message.error {
+        let detail = message.message.unwrap_or_default();
+        return Err(Error::Protocol(format!("{}: {}", error, detail)));
+    }
+
+    let audio = match message.audio {
+        Some(audio) => Bytes::from(
+            BASE64
+                .decode(audio)
+                .map_err(|err| Error::Protocol(format!("invalid audio chunk: {}", err)))?,
+        ),
+        None => Bytes::new(),
+    };
+
+    Ok(StreamInputChunk {
+        audio,
+        is_final: message.is_final.unwrap_or(false),
+        alignment: message.alignment,
+        normalized_alignment: message.normalized_alignment,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+
+    async fn next_json(socket: &mut WebSocketStream<TcpStream>) -> Value {
+        match socket.next().await.unwrap().unwrap() {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("expected a text message, got {:?}", other),
+        }
+    }
+
+    /// Plays the server side of a session and returns the messages it received.
+    async fn serve(listener: TcpListener) -> Vec<Value> {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let mut received = Vec::new();
+
+        for _ in 0..3 {
+            received.push(next_json(&mut socket).await);
+        }
+        let chunk = json!({
+            "audio": BASE64.encode(b"chunk one"),
+            "isFinal": null,
+            "alignment": {
+                "chars": ["H", "i"],
+                "charStartTimesMs": [0, 120],
+                "charsDurationsMs": [120, 80],
+            },
+            "normalizedAlignment": null,
+        });
+        socket.send(Message::Text(chunk.to_string())).await.unwrap();
+
+        received.push(next_json(&mut socket).await);
+        socket.send(Message::Text(json!({ "isFinal": true }).to_string())).await.unwrap();
+
+        match socket.next().await {
+            Some(Ok(Message::Close(_))) => {}
+            other => panic!("expected the client to close the connection, got {:?}", other),
+        }
+        received
+    }
+
+    #[tokio::test]
+    async fn session_streams_text_and_audio() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = Config::new("test_key", &format!("http://{}", listener.local_addr().unwrap()));
+        let server = tokio::spawn(serve(listener));
+
+        let options = StreamInputOptions {
+            voice_settings: Some(VoiceSettings::new(0.5, 0.75).unwrap()),
+            generation_config: Some(GenerationConfig {
+                chunk_length_schedule: vec![120, 160],
+            }),
+            pronunciation_dictionary_locators: Some(vec![PronunciationDictionaryLocator {
+                pronunciation_dictionary_id: "dict".to_string(),
+                version_id: "v1".to_string(),
+            }]),
+            ..Default::default()
+        };
+        let mut session = StreamInputSession::connect(&config, "voice", &options).await.unwrap();
+
+        session.send_text_and_trigger("Hi ").await.unwrap();
+        session.flush().await.unwrap();
+
+        let chunk = session.next().await.unwrap().unwrap();
+        assert_eq!(chunk.audio, Bytes::from_static(b"chunk one"));
+        assert!(!chunk.is_final);
+        let alignment = chunk.alignment.unwrap();
+        assert_eq!(alignment.chars, ["H", "i"]);
+        assert_eq!(alignment.char_start_times_ms, [0, 120]);
+        assert_eq!(alignment.char_durations_ms, [120, 80]);
+        assert!(chunk.normalized_alignment.is_none());
+
+        session.close().await.unwrap();
+        let last = session.next().await.unwrap().unwrap();
+        assert!(last.is_final);
+        assert!(last.audio.is_empty());
+        assert!(session.next().await.is_none());
+
+        let received = server.await.unwrap();
+        let initial = &received[0];
+        assert_eq!(initial["text"], " ");
+        assert_eq!(initial["voice_settings"]["stability"], 0.5);
+        assert_eq!(initial["voice_settings"]["similarity_boost"], 0.75);
+        assert_eq!(initial["generation_config"]["chunk_length_schedule"], json!([120, 160]));
+        assert_eq!(
+            initial["pronunciation_dictionary_locators"],
+            json!([{ "pronunciation_dictionary_id": "dict", "version_id": "v1" }])
+        );
+        assert_eq!(received[1], json!({ "text": "Hi ", "try_trigger_generation": true }));
+        assert_eq!(received[2], json!({ "text": " ", "flush": true }));
+        assert_eq!(received[3], json!({ "text": "" }));
+    }
+
+    #[test]
+    fn stream_input_url_uses_websocket_scheme_and_options() {
+        let config = Config::new("key", "https://api.elevenlabs.io");
+        let options = StreamInputOptions {
+            model_id: Some("eleven_turbo_v2".to_string()),
+            inactivity_timeout: Some(30),
+            ..Default::default()
+        };
+
+        let url = stream_input_url(&config, "voice", &options).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "wss://api.elevenlabs.io/v1/text-to-speech/voice/stream-input?model_id=eleven_turbo_v2&inactivity_timeout=30"
+        );
+        let url = stream_input_url(&config, "voice", &StreamInputOptions::default()).unwrap();
+        assert_eq!(url.query(), None);
+    }
+
+    #[test]
+    fn api_frames_with_alignment_parse() {
+        let frame = r#"{"audio":"SGk=","isFinal":null,"normalizedAlignment":{"charStartTimesMs":[0,3,7,9,11,12,13,15,17,19,21],"charsDurationsMs":[3,4,2,2,1,1,2,2,2,2,3],"chars":["H","e","l","l","o"," ","w","o","r","l","d"]},"alignment":{"charStartTimesMs":[0,3,7,9,11,12,13,15,17,19,21],"charsDurationsMs":[3,4,2,2,1,1,2,2,2,2,3],"chars":["H","e","l","l","o"," ","w","o","r","l","d"]}}"#;
+
+        let chunk = decode_message(frame).unwrap();
+        assert_eq!(chunk.audio, Bytes::from_static(b"Hi"));
+        let alignment = chunk.alignment.unwrap();
+        assert_eq!(alignment.chars.concat(), "Hello world");
+        assert_eq!(alignment.char_durations_ms, [3, 4, 2, 2, 1, 1, 2, 2, 2, 2, 3]);
+        assert_eq!(chunk.normalized_alignment.unwrap().char_start_times_ms[10], 21);
+    }
+
+    #[test]
+    fn server_errors_are_reported() {
+        let err = decode_message(r#"{"error": "invalid_api_key", "message": "bad key"}"#).unwrap_err();
+        assert!(matches!(err, Error::Protocol(message) if message == "invalid_api_key: bad key"));
+    }
+}
@@ -1,8 +1,13 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use reqwest::{Client, Response};
 use serde::{Deserialize, Serialize};
 
-use crate::api::utils::{create_request, log_error, UtilsError};
+use crate::api::utils::{create_request, log_error, send_with_retry};
 use crate::config::Config;
+use crate::error::Error;
 
 /// Represents detailed information about the user's subscription, including limits
 /// and permissions associated with the current subscription tier.
@@ -36,7 +41,8 @@ pub struct NextInvoiceDetails {
 }
 
 /// Represents information about a user, including subscription details and
-/// user-specific properties like new user status and onboarding completion.#[derive(Serialize, Deserialize, Debug)]
+/// user-specific properties like new user status and onboarding completion.
+#[derive(Serialize, Deserialize, Debug)]
 pub struct UserInfo {
     pub subscription: SubscriptionInfo,
     pub is_new_user: bool,
@@ -46,20 +52,174 @@ pub struct UserInfo {
     pub first_name: Option<String>,
 }
 
+impl SubscriptionInfo {
+    /// Returns the number of characters left until the next reset.
+    pub fn remaining_characters(&self) -> i64 {
+        (i64::from(self.character_limit) - i64::from(self.character_count)).max(0)
+    }
+
+    /// Projects when the quota runs out if characters keep being spent at the average rate
+    /// of the current cycle.
+    ///
+    /// The cycle is assumed to have started `CHARACTER_CYCLE_SECS` before
+    /// `next_character_count_reset_unix`.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_unix` - The current time, in seconds since the Unix epoch.
+    pub fn forecast(&self, now_unix: i64) -> QuotaForecast {
+        let cycle_start = self.next_character_count_reset_unix - CHARACTER_CYCLE_SECS;
+        let elapsed_days = (now_unix - cycle_start).max(1) as f64 / SECS_PER_DAY as f64;
+        self.forecast_with_burn_rate(now_unix, f64::from(self.character_count) / elapsed_days)
+    }
+
+    /// Projects when the quota runs out if `characters_per_day` characters are spent every day,
+    /// e.g. a rate computed with [`UsageStats::daily_burn_rate`] over a recent window.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_unix` - The current time, in seconds since the Unix epoch.
+    /// * `characters_per_day` - The expected burn rate.
+    pub fn forecast_with_burn_rate(&self, now_unix: i64, characters_per_day: f64) -> QuotaForecast {
+        let remaining_characters = self.remaining_characters();
+        let exhausted_at_unix = if remaining_characters == 0 {
+            Some(now_unix)
+        } else if characters_per_day > 0.0 {
+            let days_left = remaining_characters as f64 / characters_per_day;
+            Some(now_unix + (days_left * SECS_PER_DAY as f64) as i64)
+        } else {
+            None
+        };
+
+        QuotaForecast {
+            remaining_characters,
+            characters_per_day,
+            exhausted_at_unix,
+            resets_at_unix: self.next_character_count_reset_unix,
+            runs_out_before_reset: exhausted_at_unix.is_some_and(|at| at < self.next_character_count_reset_unix),
+        }
+    }
+}
+
+/// The length of a character quota cycle assumed by [`SubscriptionInfo::forecast`].
+pub const CHARACTER_CYCLE_SECS: i64 = 30 * SECS_PER_DAY;
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// A projection of when the character quota runs out.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuotaForecast {
+    /// The characters left until the next reset.
+    pub remaining_characters: i64,
+    /// The burn rate the projection is based on.
+    pub characters_per_day: f64,
+    /// When the quota runs out at that rate, in seconds since the Unix epoch, or `None` if
+    /// nothing is being spent.
+    pub exhausted_at_unix: Option<i64>,
+    /// When the character count resets, in seconds since the Unix epoch.
+    pub resets_at_unix: i64,
+    /// Whether the quota runs out before it resets.
+    pub runs_out_before_reset: bool,
+}
+
+/// How character usage is broken down.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageBreakdown {
+    /// A single series named `All`.
+    None,
+    Voice,
+    User,
+    ApiKeys,
+    Model,
+    ProductType,
+}
+
+/// The width of the buckets character usage is aggregated in.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationInterval {
+    Hour,
+    Day,
+    Week,
+    Month,
+    /// A single bucket spanning the whole range.
+    Cumulative,
+}
+
+/// Query parameters of the character usage statistics.
+#[derive(Serialize, Debug, Clone)]
+pub struct UsageStatsParams {
+    /// The start of the range, in milliseconds since the Unix epoch.
+    pub start_unix: i64,
+    /// The end of the range, in milliseconds since the Unix epoch.
+    pub end_unix: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub breakdown_type: Option<UsageBreakdown>,
+    /// Defaults to `Day`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregation_interval: Option<AggregationInterval>,
+    /// Includes the usage of the whole workspace instead of only this user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_workspace_metrics: Option<bool>,
+}
+
+/// Character usage over time.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UsageStats {
+    /// The start of every bucket, in milliseconds since the Unix epoch.
+    pub time: Vec<i64>,
+    /// The characters spent in every bucket, keyed by voice, API key, model, etc. depending on
+    /// the breakdown. Every series has one value per entry of `time`.
+    pub usage: HashMap<String, Vec<f64>>,
+}
+
+impl UsageStats {
+    /// Returns the characters spent in every bucket, summed over all series.
+    pub fn totals(&self) -> Vec<f64> {
+        (0..self.time.len())
+            .map(|index| self.usage.values().filter_map(|series| series.get(index)).sum())
+            .collect()
+    }
+
+    /// Returns the characters spent over the whole range, summed over all series.
+    pub fn total(&self) -> f64 {
+        self.usage.values().flatten().sum()
+    }
+
+    /// Returns the total of every series, e.g. the characters spent per voice.
+    pub fn totals_by_key(&self) -> HashMap<String, f64> {
+        self.usage
+            .iter()
+            .map(|(key, series)| (key.clone(), series.iter().sum()))
+            .collect()
+    }
+
+    /// Returns the average number of characters spent per day between `start_unix_ms` and
+    /// `end_unix_ms`, the range the statistics were requested for.
+    pub fn daily_burn_rate(&self, start_unix_ms: i64, end_unix_ms: i64) -> f64 {
+        let days = (end_unix_ms - start_unix_ms).max(1) as f64 / (SECS_PER_DAY * 1000) as f64;
+        self.total() / days
+    }
+}
+
 /// Provides functionality for interacting with the ElevenLabs User API endpoints.
 /// Allows retrieval of user information and subscription details.
+#[derive(Clone)]
 pub struct UserClient {
     client: Client,
-    config: Config,
+    config: Arc<Config>,
 }
 
 impl UserClient {
     /// Creates a new `UserClient` with the provided configuration.
     pub fn new(config: Config) -> Self {
-        UserClient {
-            client: Client::new(),
-            config,
-        }
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `UserClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
+        UserClient { client, config }
     }
     
     /// Fetches detailed information about the user from the ElevenLabs API.
@@ -67,20 +227,25 @@ impl UserClient {
     /// # Returns
     ///
     /// A `Result` type that, on success, contains `UserInfo` representing the user's details,
-    /// or `UtilsError` on failure.
+    /// or `Error` on failure.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, user::UserClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
     /// let user_client = UserClient::new(config);
-    /// let user_info = user_client.get_user_info().await.unwrap();
-    /// println!("User's first name: {}", user_info.first_name);
+    /// let user_info = user_client.get_user_info().await?;
+    /// println!("User's first name: {:?}", user_info.first_name);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn get_user_info(&self) -> Result<UserInfo, UtilsError> {
+    pub async fn get_user_info(&self) -> Result<UserInfo, Error> {
         let url = format!("{}/v1/user", &self.config.api_url);
         let response = self.send_request(url).await?;
 
-        response.json::<UserInfo>().await.map_err(UtilsError::Http)
+        Ok(response.json::<UserInfo>().await?)
     }
 
     /// Retrieves the user's subscription information from the ElevenLabs API.
@@ -88,20 +253,99 @@ impl UserClient {
     /// # Returns
     ///
     /// A `Result` type that, on success, contains `SubscriptionInfo` detailing the subscription,
-    /// or `UtilsError` on failure.
+    /// or `Error` on failure.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, user::UserClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
     /// let user_client = UserClient::new(config);
-    /// let subscription_info = user_client.get_user_subscription_info().await.unwrap();
+    /// let subscription_info = user_client.get_user_subscription_info().await?;
     /// println!("Subscription tier: {}", subscription_info.tier);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn get_user_subscription_info(&self) -> Result<SubscriptionInfo, UtilsError> {
+    pub async fn get_user_subscription_info(&self) -> Result<SubscriptionInfo, Error> {
         let url = format!("{}/v1/user/subscription", &self.config.api_url);
         let response = self.send_request(url).await?;
 
-        response.json::<SubscriptionInfo>().await.map_err(UtilsError::Http)
+        Ok(response.json::<SubscriptionInfo>().await?)
+    }
+
+    /// Retrieves the characters spent over a time range, broken down by voice, API key, model, etc.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The time range, breakdown and aggregation interval.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` type that, on success, contains the `UsageStats`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, user::{UsageBreakdown, UsageStatsParams, UserClient}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let user_client = UserClient::new(config);
+    /// let params = UsageStatsParams {
+    ///     start_unix: 1_717_200_000_000,
+    ///     end_unix: 1_719_792_000_000,
+    ///     breakdown_type: Some(UsageBreakdown::Voice),
+    ///     aggregation_interval: None,
+    ///     include_workspace_metrics: None,
+    /// };
+    /// let stats = user_client.get_character_usage(&params).await?;
+    /// for (voice_id, characters) in stats.totals_by_key() {
+    ///     println!("{}: {}", voice_id, characters);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_character_usage(&self, params: &UsageStatsParams) -> Result<UsageStats, Error> {
+        let url = format!("{}/v1/usage/character-stats", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .query(params))
+        })
+        .await?;
+
+        Ok(response.json::<UsageStats>().await?)
+    }
+
+    /// Projects when the character quota runs out at the average burn rate of the current cycle.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` type that, on success, contains the `QuotaForecast`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, user::UserClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let user_client = UserClient::new(config);
+    /// let forecast = user_client.forecast_quota().await?;
+    /// if forecast.runs_out_before_reset {
+    ///     println!("Quota runs out at {:?}", forecast.exhausted_at_unix);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn forecast_quota(&self) -> Result<QuotaForecast, Error> {
+        let subscription = self.get_user_subscription_info().await?;
+        let now_unix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default();
+
+        Ok(subscription.forecast(now_unix))
     }
 
     /// Sends a GET request to the provided URL and returns the HTTP response.
@@ -114,20 +358,109 @@ impl UserClient {
     ///
     /// # Returns
     ///
-    /// A `Result` type that, on success, contains the `Response` object, or `UtilsError` on failure.
-    async fn send_request(&self, url: String) -> Result<Response, UtilsError> {
-        let response = create_request(&self.client, reqwest::Method::GET, &url)
-            .header("xi-api-key", &self.config.api_key)
-            .send()
-            .await
-            .map_err(UtilsError::Http)?;
+    /// A `Result` type that, on success, contains the `Response` object, or `Error` on failure.
+    async fn send_request(&self, url: String) -> Result<Response, Error> {
+        send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await
+        .map_err(|err| {
+            log_error(&format!("🚨 Failed to send request: {}", err));
+            err
+        })
+    }
+}
 
-        if response.status().is_success() {
-            Ok(response)
-        } else {
-            let error_msg = format!("🚨 Failed to send request: HTTP {}", response.status());
-            log_error(&error_msg);
-            Err(UtilsError::Custom(error_msg))
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    /// A subscription whose cycle started ten days ago and resets in twenty.
+    fn subscription(character_count: i32, character_limit: i32) -> SubscriptionInfo {
+        serde_json::from_value(serde_json::json!({
+            "tier": "creator",
+            "character_count": character_count,
+            "character_limit": character_limit,
+            "can_extend_character_limit": true,
+            "allowed_to_extend_character_limit": true,
+            "next_character_count_reset_unix": NOW + 20 * SECS_PER_DAY,
+            "voice_limit": 30,
+            "max_voice_add_edits": 95,
+            "voice_add_edit_counter": 0,
+            "professional_voice_limit": 1,
+            "can_extend_voice_limit": false,
+            "can_use_instant_voice_cloning": true,
+            "can_use_professional_voice_cloning": true,
+            "currency": "usd",
+            "status": "active",
+            "billing_period": "monthly_period",
+            "next_invoice": { "amount_due_cents": 2200, "next_payment_attempt_unix": NOW + 20 * SECS_PER_DAY },
+            "has_open_invoices": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn quota_running_out_before_the_reset() {
+        let forecast = subscription(50_000, 100_000).forecast(NOW);
+        assert_eq!(forecast.remaining_characters, 50_000);
+        assert_eq!(forecast.characters_per_day, 5_000.0);
+        assert_eq!(forecast.exhausted_at_unix, Some(NOW + 10 * SECS_PER_DAY));
+        assert!(forecast.runs_out_before_reset);
+    }
+
+    #[test]
+    fn quota_lasting_past_the_reset() {
+        let forecast = subscription(10_000, 100_000).forecast(NOW);
+        assert_eq!(forecast.characters_per_day, 1_000.0);
+        assert_eq!(forecast.exhausted_at_unix, Some(NOW + 90 * SECS_PER_DAY));
+        assert_eq!(forecast.resets_at_unix, NOW + 20 * SECS_PER_DAY);
+        assert!(!forecast.runs_out_before_reset);
+    }
+
+    #[test]
+    fn zero_burn_rate_never_runs_out() {
+        let forecast = subscription(0, 100_000).forecast(NOW);
+        assert_eq!(forecast.characters_per_day, 0.0);
+        assert_eq!(forecast.exhausted_at_unix, None);
+        assert!(!forecast.runs_out_before_reset);
+
+        let forecast = subscription(50_000, 100_000).forecast_with_burn_rate(NOW, 0.0);
+        assert_eq!(forecast.exhausted_at_unix, None);
+    }
+
+    #[test]
+    fn exceeded_quota_is_exhausted_now() {
+        let forecast = subscription(120_000, 100_000).forecast_with_burn_rate(NOW, 0.0);
+        assert_eq!(forecast.remaining_characters, 0);
+        assert_eq!(forecast.exhausted_at_unix, Some(NOW));
+        assert!(forecast.runs_out_before_reset);
+    }
+
+    #[test]
+    fn burn_rate_over_a_partial_window() {
+        let stats = UsageStats {
+            time: vec![0, 3_600_000, 7_200_000],
+            usage: HashMap::from([
+                ("voice_a".to_string(), vec![100.0, 200.0, 300.0]),
+                // A series missing its last bucket.
+                ("voice_b".to_string(), vec![50.0, 250.0]),
+            ]),
+        };
+        assert_eq!(stats.totals(), [150.0, 450.0, 300.0]);
+        assert_eq!(stats.total(), 900.0);
+        assert_eq!(stats.totals_by_key()["voice_a"], 600.0);
+        assert_eq!(stats.totals_by_key()["voice_b"], 300.0);
+
+        // A day and a half.
+        let end = 36 * 60 * 60 * 1000;
+        assert_eq!(stats.daily_burn_rate(0, end), 600.0);
+
+        let forecast = subscription(90_000, 100_000).forecast_with_burn_rate(NOW, stats.daily_burn_rate(0, end));
+        assert_eq!(forecast.exhausted_at_unix, Some(NOW + 10_000 * SECS_PER_DAY / 600));
+        assert!(forecast.runs_out_before_reset);
     }
 }
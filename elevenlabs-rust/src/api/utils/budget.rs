@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::header::HeaderMap;
+
+use crate::api::user::SubscriptionInfo;
+use crate::error::Error;
+
+/// A limit of a [`CharacterBudget`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BudgetScope {
+    /// Every character spent through the budget.
+    Total,
+    /// The characters spent since midnight UTC.
+    Daily,
+    /// The characters spent by one job.
+    Job(String),
+    /// The characters spent by calls carrying one tag.
+    Tag(String),
+}
+
+impl fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetScope::Total => write!(f, "total"),
+            BudgetScope::Daily => write!(f, "daily"),
+            BudgetScope::Job(job) => write!(f, "job {}", job),
+            BudgetScope::Tag(tag) => write!(f, "tag {}", tag),
+        }
+    }
+}
+
+/// The details of a call refused by a [`CharacterBudget`].
+#[derive(Debug, Clone)]
+pub struct BudgetExceeded {
+    /// The limit the call would have exceeded.
+    pub scope: BudgetScope,
+    pub limit: u64,
+    /// The characters already spent within the limit.
+    pub used: u64,
+    /// The characters the call would have spent.
+    pub requested: u64,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} budget of {} characters has {} left, {} requested",
+            self.scope,
+            self.limit,
+            self.limit.saturating_sub(self.used),
+            self.requested
+        )
+    }
+}
+
+/// Characters charged by [`CharacterBudget::charge`], to be handed back to
+/// [`CharacterBudget::refund`] if the call fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetCharge {
+    pub characters: u64,
+    /// The UTC day of the charge, in days since the Unix epoch.
+    pub day: u64,
+}
+
+/// What the calls made with a `Config` are counted as, besides the total and daily limits.
+#[derive(Debug, Default, Clone)]
+pub struct BudgetLabels {
+    pub job: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// A client-side cap on the characters spent, checked before requests reach the network.
+///
+/// Attach it with [`Config::with_budget`](crate::Config::with_budget). Text-to-Speech calls,
+/// including streaming and WebSocket sessions, are charged the length of their text up front
+/// and refused with `Error::BudgetExceeded` when a limit would be exceeded. Speech-to-Speech
+/// calls are refused once a limit is reached and charged the `character-cost` the API reports.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use elevenlabs_rust::{CharacterBudget, Config};
+/// let budget = Arc::new(
+///     CharacterBudget::new()
+///         .with_daily_limit(200_000)
+///         .with_job_limit("nightly-render", 50_000),
+/// );
+/// let config = Config::new("your_api_key", "https://api.elevenlabs.io")
+///     .with_budget(budget)
+///     .with_budget_job("nightly-render");
+/// ```
+#[derive(Debug, Default)]
+pub struct CharacterBudget {
+    total_limit: Option<u64>,
+    daily_limit: Option<u64>,
+    job_limits: HashMap<String, u64>,
+    tag_limits: HashMap<String, u64>,
+    usage: Mutex<Usage>,
+}
+
+#[derive(Debug, Default)]
+struct Usage {
+    total: u64,
+    day: u64,
+    daily: u64,
+    jobs: HashMap<String, u64>,
+    tags: HashMap<String, u64>,
+}
+
+impl Usage {
+    /// Starts a new daily count when the UTC day has changed since the last call.
+    fn roll_over(&mut self, today: u64) {
+        if self.day != today {
+            self.day = today;
+            self.daily = 0;
+        }
+    }
+
+    fn add(&mut self, labels: &BudgetLabels, characters: u64) {
+        self.total += characters;
+        self.daily += characters;
+        if let Some(job) = &labels.job {
+            *self.jobs.entry(job.clone()).or_default() += characters;
+        }
+        for tag in &labels.tags {
+            *self.tags.entry(tag.clone()).or_default() += characters;
+        }
+    }
+
+    fn subtract(&mut self, labels: &BudgetLabels, charge: BudgetCharge) {
+        let characters = charge.characters;
+        self.total = self.total.saturating_sub(characters);
+        // A charge from an earlier day was never counted in today's usage.
+        if charge.day == self.day {
+            self.daily = self.daily.saturating_sub(characters);
+        }
+        if let Some(used) = labels.job.as_ref().and_then(|job| self.jobs.get_mut(job)) {
+            *used = used.saturating_sub(characters);
+        }
+        for tag in &labels.tags {
+            if let Some(used) = self.tags.get_mut(tag) {
+                *used = used.saturating_sub(characters);
+            }
+        }
+    }
+}
+
+impl CharacterBudget {
+    /// Creates a budget without limits.
+    pub fn new() -> Self {
+        CharacterBudget::default()
+    }
+
+    /// Creates a budget capped at the characters left in the current subscription cycle.
+    pub fn from_subscription(subscription: &SubscriptionInfo) -> Self {
+        CharacterBudget::new().with_total_limit(subscription.remaining_characters() as u64)
+    }
+
+    /// Caps every character spent through the budget.
+    pub fn with_total_limit(mut self, characters: u64) -> Self {
+        self.total_limit = Some(characters);
+        self
+    }
+
+    /// Caps the characters spent per UTC day.
+    pub fn with_daily_limit(mut self, characters: u64) -> Self {
+        self.daily_limit = Some(characters);
+        self
+    }
+
+    /// Caps the characters spent by calls labelled with `job`.
+    pub fn with_job_limit(mut self, job: &str, characters: u64) -> Self {
+        self.job_limits.insert(job.to_string(), characters);
+        self
+    }
+
+    /// Caps the characters spent by calls labelled with `tag`.
+    pub fn with_tag_limit(mut self, tag: &str, characters: u64) -> Self {
+        self.tag_limits.insert(tag.to_string(), characters);
+        self
+    }
+
+    /// Returns the characters spent within `scope` so far.
+    pub fn used(&self, scope: &BudgetScope) -> u64 {
+        let mut usage = self.usage.lock().unwrap();
+        usage.roll_over(today());
+        match scope {
+            BudgetScope::Total => usage.total,
+            BudgetScope::Daily => usage.daily,
+            BudgetScope::Job(job) => usage.jobs.get(job).copied().unwrap_or_default(),
+            BudgetScope::Tag(tag) => usage.tags.get(tag).copied().unwrap_or_default(),
+        }
+    }
+
+    /// Charges `characters` to the budget, or refuses them without charging anything if a
+    /// limit that applies to `labels` would be exceeded.
+    pub fn charge(&self, labels: &BudgetLabels, characters: u64) -> Result<BudgetCharge, Error> {
+        self.charge_on(today(), labels, characters)
+    }
+
+    /// Returns the characters of a charge whose call failed. The daily count is only reduced
+    /// if the charge was made the same UTC day.
+    pub fn refund(&self, labels: &BudgetLabels, charge: BudgetCharge) {
+        self.refund_on(today(), labels, charge)
+    }
+
+    /// Records characters spent by a call whose cost is only known afterwards, even if this
+    /// exceeds a limit.
+    pub fn record(&self, labels: &BudgetLabels, characters: u64) {
+        let mut usage = self.usage.lock().unwrap();
+        usage.roll_over(today());
+        usage.add(labels, characters);
+    }
+
+    /// Refuses a call if a limit that applies to `labels` has been reached.
+    pub fn check(&self, labels: &BudgetLabels) -> Result<(), Error> {
+        let mut usage = self.usage.lock().unwrap();
+        usage.roll_over(today());
+        self.check_limits(&usage, labels, 1)
+    }
+
+    fn charge_on(&self, today: u64, labels: &BudgetLabels, characters: u64) -> Result<BudgetCharge, Error> {
+        let mut usage = self.usage.lock().unwrap();
+        usage.roll_over(today);
+        self.check_limits(&usage, labels, characters)?;
+        usage.add(labels, characters);
+        Ok(BudgetCharge { characters, day: today })
+    }
+
+    fn refund_on(&self, today: u64, labels: &BudgetLabels, charge: BudgetCharge) {
+        let mut usage = self.usage.lock().unwrap();
+        usage.roll_over(today);
+        usage.subtract(labels, charge);
+    }
+
+    fn check_limits(&self, usage: &Usage, labels: &BudgetLabels, requested: u64) -> Result<(), Error> {
+        let mut limits = vec![
+            (BudgetScope::Total, self.total_limit, usage.total),
+            (BudgetScope::Daily, self.daily_limit, usage.daily),
+        ];
+        if let Some(job) = &labels.job {
+            let used = usage.jobs.get(job).copied().unwrap_or_default();
+            limits.push((BudgetScope::Job(job.clone()), self.job_limits.get(job).copied(), used));
+        }
+        for tag in &labels.tags {
+            let used = usage.tags.get(tag).copied().unwrap_or_default();
+            limits.push((BudgetScope::Tag(tag.clone()), self.tag_limits.get(tag).copied(), used));
+        }
+
+        for (scope, limit, used) in limits {
+            if let Some(limit) = limit {
+                if used + requested > limit {
+                    return Err(Error::BudgetExceeded(BudgetExceeded {
+                        scope,
+                        limit,
+                        used,
+                        requested,
+                    }));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses the `character-cost` header the API sends with every billed response.
+pub(crate) fn parse_character_cost(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get("character-cost")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Returns the number of days since the Unix epoch, in UTC.
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / (24 * 60 * 60))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    const DAY: u64 = 20_000;
+
+    fn labels(job: Option<&str>, tags: &[&str]) -> BudgetLabels {
+        BudgetLabels {
+            job: job.map(str::to_string),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    fn exceeded(result: Result<BudgetCharge, Error>) -> BudgetExceeded {
+        match result {
+            Err(Error::BudgetExceeded(exceeded)) => exceeded,
+            other => panic!("expected the budget to be exceeded, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn total_limit_refuses_without_charging() {
+        let budget = CharacterBudget::new().with_total_limit(100);
+        let none = BudgetLabels::default();
+
+        budget.charge_on(DAY, &none, 60).unwrap();
+        let refused = exceeded(budget.charge_on(DAY, &none, 41));
+        assert_eq!(refused.scope, BudgetScope::Total);
+        assert_eq!((refused.limit, refused.used, refused.requested), (100, 60, 41));
+
+        budget.charge_on(DAY, &none, 40).unwrap();
+        assert_eq!(budget.usage.lock().unwrap().total, 100);
+    }
+
+    #[test]
+    fn daily_limit_resets_at_midnight() {
+        let budget = CharacterBudget::new().with_daily_limit(100);
+        let none = BudgetLabels::default();
+
+        budget.charge_on(DAY, &none, 100).unwrap();
+        assert_eq!(exceeded(budget.charge_on(DAY, &none, 1)).scope, BudgetScope::Daily);
+
+        budget.charge_on(DAY + 1, &none, 100).unwrap();
+        let usage = budget.usage.lock().unwrap();
+        assert_eq!((usage.total, usage.daily), (200, 100));
+    }
+
+    #[test]
+    fn job_and_tag_limits_only_apply_to_their_calls() {
+        let budget = CharacterBudget::new()
+            .with_job_limit("render", 50)
+            .with_tag_limit("preview", 30);
+        let render = labels(Some("render"), &[]);
+        let preview = labels(None, &["preview", "other"]);
+
+        budget.charge_on(DAY, &render, 50).unwrap();
+        assert_eq!(
+            exceeded(budget.charge_on(DAY, &render, 1)).scope,
+            BudgetScope::Job("render".to_string())
+        );
+        budget.charge_on(DAY, &labels(Some("other"), &[]), 1_000).unwrap();
+
+        budget.charge_on(DAY, &preview, 30).unwrap();
+        assert_eq!(
+            exceeded(budget.charge_on(DAY, &labels(Some("render"), &["preview"]), 1)).scope,
+            BudgetScope::Job("render".to_string())
+        );
+        assert_eq!(
+            exceeded(budget.charge_on(DAY, &preview, 1)).scope,
+            BudgetScope::Tag("preview".to_string())
+        );
+
+        assert_eq!(budget.used(&BudgetScope::Job("render".to_string())), 50);
+        assert_eq!(budget.used(&BudgetScope::Tag("other".to_string())), 30);
+    }
+
+    #[test]
+    fn refund_returns_every_scope() {
+        let budget = CharacterBudget::new().with_daily_limit(100).with_job_limit("render", 100);
+        let render = labels(Some("render"), &["preview"]);
+
+        let charge = budget.charge_on(DAY, &render, 80).unwrap();
+        budget.refund_on(DAY, &render, charge);
+
+        let usage = budget.usage.lock().unwrap();
+        assert_eq!((usage.total, usage.daily), (0, 0));
+        assert_eq!(usage.jobs["render"], 0);
+        assert_eq!(usage.tags["preview"], 0);
+    }
+
+    #[test]
+    fn refund_after_midnight_leaves_the_new_day_alone() {
+        let budget = CharacterBudget::new().with_daily_limit(100);
+        let none = BudgetLabels::default();
+
+        let yesterday = budget.charge_on(DAY, &none, 80).unwrap();
+        budget.charge_on(DAY + 1, &none, 90).unwrap();
+        budget.refund_on(DAY + 1, &none, yesterday);
+
+        let usage = budget.usage.lock().unwrap();
+        assert_eq!((usage.total, usage.daily), (90, 90));
+        drop(usage);
+        assert_eq!(exceeded(budget.charge_on(DAY + 1, &none, 11)).scope, BudgetScope::Daily);
+    }
+
+    #[test]
+    fn refund_of_a_charge_before_a_roll_over_does_not_underflow() {
+        let budget = CharacterBudget::new().with_daily_limit(100);
+        let none = BudgetLabels::default();
+
+        let yesterday = budget.charge_on(DAY, &none, 80).unwrap();
+        budget.refund_on(DAY + 1, &none, yesterday);
+
+        let usage = budget.usage.lock().unwrap();
+        assert_eq!((usage.day, usage.total, usage.daily), (DAY + 1, 0, 0));
+    }
+
+    #[test]
+    fn recorded_costs_can_exceed_limits_and_block_further_calls() {
+        let budget = CharacterBudget::new().with_total_limit(100);
+        let none = BudgetLabels::default();
+
+        budget.check(&none).unwrap();
+        budget.record(&none, 150);
+        assert_eq!(budget.used(&BudgetScope::Total), 150);
+        assert!(matches!(budget.check(&none), Err(Error::BudgetExceeded(_))));
+    }
+
+    #[test]
+    fn parses_the_character_cost_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_character_cost(&headers), None);
+
+        headers.insert("character-cost", HeaderValue::from_static(" 42 "));
+        assert_eq!(parse_character_cost(&headers), Some(42));
+
+        headers.insert("character-cost", HeaderValue::from_static("n/a"));
+        assert_eq!(parse_character_cost(&headers), None);
+    }
+}
@@ -4,6 +4,7 @@ use std::{fmt, error};
 pub enum UtilsError {
     Http(reqwest::Error),
     Io(std::io::Error),
+    Custom(String),
 }
 
 impl fmt::Display for UtilsError {
@@ -11,6 +12,7 @@ impl fmt::Display for UtilsError {
         match *self {
             UtilsError::Http(ref err) => write!(f, "HTTP Error: {}", err),
             UtilsError::Io(ref err) => write!(f, "IO Error: {}", err),
+            UtilsError::Custom(ref msg) => write!(f, "{}", msg),
         }
     }
 }
@@ -20,6 +22,7 @@ impl error::Error for UtilsError {
         match *self {
             UtilsError::Http(ref err) => Some(err),
             UtilsError::Io(ref err) => Some(err),
+            UtilsError::Custom(_) => None,
         }
     }
 }
@@ -0,0 +1,19 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+
+/// Returns the temporary path `path` is written to before being renamed into place.
+pub(crate) fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Writes `contents` to `path` so that readers see either the old or the new file, never a
+/// partial one.
+pub(crate) async fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    let temp_path = temp_path(path);
+    tokio::fs::write(&temp_path, contents).await?;
+    tokio::fs::rename(&temp_path, path).await?;
+    Ok(())
+}
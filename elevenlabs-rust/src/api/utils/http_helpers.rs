@@ -1,7 +1,18 @@
-use reqwest::{Client, RequestBuilder};
+use reqwest::{Client, RequestBuilder, Response};
+
+use crate::error::Error;
 
 pub fn create_request(client: &Client, method: reqwest::Method, url: &str) -> RequestBuilder {
     client.request(method, url)
           .header("Accept", "application/json")
           .header("Content-Type", "application/json")
 }
+
+/// Passes successful responses through and turns every other response into a typed `Error`.
+pub async fn ensure_success(response: Response) -> Result<Response, Error> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(Error::from_response(response).await)
+    }
+}
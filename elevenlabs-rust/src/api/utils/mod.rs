@@ -1,13 +1,26 @@
 // Import individual modules to make them accessible under the `utils` namespace
+pub mod budget;
 pub mod config_loader;
 pub mod errors;
+pub mod files;
 pub mod http_helpers;
 pub mod logging;
+pub mod pagination;
+pub mod retry;
 pub mod serde_helpers;
+pub mod streaming;
+pub mod text_chunking;
+pub mod upload;
 
 // Re-export commonly used functions or types if desired
 pub use self::logging::{setup_logging, log_info, log_warning, log_error};
-pub use self::http_helpers::create_request;
+pub use self::http_helpers::{create_request, ensure_success};
 pub use self::errors::UtilsError;
+pub use self::budget::{BudgetCharge, BudgetExceeded, BudgetLabels, BudgetScope, CharacterBudget};
+pub use self::retry::{send_with_retry, RetryPolicy};
+pub use self::text_chunking::split_text;
+pub use self::upload::{detect_mime_type, FileUpload, PreparedUpload};
 pub use self::config_loader::{load_api_key, load_api_url};
-pub use self::serde_helpers::{serialize, deserialize};
+pub use self::serde_helpers::{serialize, deserialize, null_as_default};
+pub use self::pagination::paginate;
+pub use self::streaming::{audio_stream, json_lines, write_stream, AudioStream};
@@ -0,0 +1,34 @@
+use std::future::Future;
+
+use futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+use crate::error::Error;
+
+/// Turns a paginated endpoint into a stream of its items.
+///
+/// `fetch` is called with the cursor of the page to load (`None` for the first page) and
+/// returns the items of that page and the cursor of the next one, or `None` on the last page.
+/// Pages are only requested as the stream is consumed.
+pub fn paginate<T, C, F, Fut>(mut fetch: F) -> BoxStream<'static, Result<T, Error>>
+where
+    T: Send + 'static,
+    C: Send + 'static,
+    F: FnMut(Option<C>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(Vec<T>, Option<C>), Error>> + Send + 'static,
+{
+    stream::try_unfold(Some(None), move |cursor: Option<Option<C>>| {
+        let page = cursor.map(&mut fetch);
+        async move {
+            match page {
+                Some(page) => {
+                    let (items, next) = page.await?;
+                    Ok::<_, Error>(Some((items, next.map(Some))))
+                }
+                None => Ok(None),
+            }
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+    .boxed()
+}
@@ -0,0 +1,254 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+use crate::api::utils::log_warning;
+use crate::error::Error;
+
+/// Controls how failed requests are retried.
+///
+/// Rate limited requests (HTTP 429) and requests that never reached the API are
+/// retried for every call, because the API did not act on them. Server errors and
+/// timeouts are only retried for calls that are safe to repeat.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// The delay before the first retry.
+    pub initial_backoff: Duration,
+    /// The upper bound of the delay between two attempts.
+    pub max_backoff: Duration,
+    /// The factor the delay grows by after each attempt.
+    pub multiplier: f64,
+    /// Whether to randomize each delay to spread out retries from concurrent callers.
+    pub jitter: bool,
+    /// Whether to wait for the `Retry-After` delay sent with a 429 response.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Returns the delay before the retry that follows attempt number `attempt` (starting at 1).
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(attempt.saturating_sub(1) as i32);
+        let backoff = self.initial_backoff.mul_f64(factor).min(self.max_backoff);
+
+        if self.jitter && !backoff.is_zero() {
+            // "Equal jitter": keep half of the delay and randomize the rest.
+            let half = backoff / 2;
+            half + half.mul_f64(rand::thread_rng().gen::<f64>())
+        } else {
+            backoff
+        }
+    }
+
+    /// Returns how long to wait before retrying after `error`, or `None` if it must not be retried.
+    fn retry_delay(&self, error: &Error, attempt: u32, idempotent: bool) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        match error {
+            Error::RateLimited { retry_after, .. } => {
+                let backoff = self.backoff(attempt);
+                match retry_after {
+                    Some(retry_after) if self.respect_retry_after => Some((*retry_after).max(backoff)),
+                    _ => Some(backoff),
+                }
+            }
+            Error::Server(api_error) if idempotent && is_transient(api_error.status) => Some(self.backoff(attempt)),
+            Error::Http(err) if err.is_connect() || (idempotent && err.is_timeout()) => Some(self.backoff(attempt)),
+            _ => None,
+        }
+    }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Sends a request, retrying it according to `policy`, and returns the first successful response.
+///
+/// `build` is called once per attempt, so request bodies that cannot be cloned (such as
+/// multipart forms) are rebuilt from scratch for every retry. `idempotent` marks calls that
+/// are safe to repeat after the API may already have acted on them.
+pub async fn send_with_retry<F>(policy: &RetryPolicy, idempotent: bool, mut build: F) -> Result<Response, Error>
+where
+    F: FnMut() -> Result<RequestBuilder, Error>,
+{
+    let mut attempt = 1;
+
+    loop {
+        let result = match build()?.send().await {
+            Ok(response) if response.status().is_success() => return Ok(response),
+            Ok(response) => Error::from_response(response).await,
+            Err(err) => Error::Http(err),
+        };
+
+        match policy.retry_delay(&result, attempt, idempotent) {
+            Some(delay) => {
+                log_warning(&format!(
+                    "Attempt {} of {} failed ({}), retrying in {:?}",
+                    attempt, policy.max_attempts, result, delay
+                ));
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            None => return Err(result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+            multiplier: 2.0,
+            jitter: false,
+            respect_retry_after: true,
+        }
+    }
+
+    fn api_error(status: StatusCode) -> ApiError {
+        ApiError {
+            status,
+            request_id: None,
+            body: None,
+            raw_body: String::new(),
+        }
+    }
+
+    fn rate_limited(retry_after: Option<Duration>) -> Error {
+        Error::RateLimited {
+            error: api_error(StatusCode::TOO_MANY_REQUESTS),
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_the_cap() {
+        let policy = policy();
+        let delays: Vec<_> = (1..=5).map(|attempt| policy.backoff(attempt)).collect();
+
+        assert_eq!(
+            delays,
+            [100, 200, 400, 500, 500].map(Duration::from_millis).to_vec()
+        );
+    }
+
+    #[test]
+    fn backoff_never_shrinks_with_a_multiplier_below_one() {
+        let policy = RetryPolicy {
+            multiplier: 0.5,
+            ..policy()
+        };
+
+        assert_eq!(policy.backoff(3), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn jitter_keeps_at_least_half_of_the_delay() {
+        let policy = RetryPolicy {
+            jitter: true,
+            ..policy()
+        };
+
+        for _ in 0..100 {
+            let delay = policy.backoff(2);
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn rate_limits_wait_for_the_longer_of_retry_after_and_backoff() {
+        let policy = policy();
+
+        assert_eq!(
+            policy.retry_delay(&rate_limited(Some(Duration::from_secs(3))), 1, false),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            policy.retry_delay(&rate_limited(Some(Duration::from_millis(10))), 1, false),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.retry_delay(&rate_limited(None), 2, false),
+            Some(Duration::from_millis(200))
+        );
+    }
+
+    #[test]
+    fn retry_after_can_be_ignored() {
+        let policy = RetryPolicy {
+            respect_retry_after: false,
+            ..policy()
+        };
+
+        assert_eq!(
+            policy.retry_delay(&rate_limited(Some(Duration::from_secs(3))), 1, false),
+            Some(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn server_errors_are_only_retried_for_idempotent_calls() {
+        let policy = policy();
+        let error = Error::Server(api_error(StatusCode::SERVICE_UNAVAILABLE));
+
+        assert_eq!(policy.retry_delay(&error, 1, true), Some(Duration::from_millis(100)));
+        assert_eq!(policy.retry_delay(&error, 1, false), None);
+    }
+
+    #[test]
+    fn permanent_errors_are_not_retried() {
+        let policy = policy();
+
+        assert_eq!(
+            policy.retry_delay(&Error::Server(api_error(StatusCode::NOT_IMPLEMENTED)), 1, true),
+            None
+        );
+        assert_eq!(policy.retry_delay(&Error::NotFound(api_error(StatusCode::NOT_FOUND)), 1, true), None);
+    }
+
+    #[test]
+    fn attempts_stop_at_max_attempts() {
+        let policy = policy();
+
+        assert!(policy.retry_delay(&rate_limited(None), 4, true).is_some());
+        assert_eq!(policy.retry_delay(&rate_limited(None), 5, true), None);
+        assert_eq!(RetryPolicy::none().retry_delay(&rate_limited(None), 1, true), None);
+    }
+}
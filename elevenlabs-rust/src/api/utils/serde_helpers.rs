@@ -1,4 +1,4 @@
-use serde::{Serialize, Deserialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Result;
 
 pub fn serialize<T: Serialize>(item: &T) -> Result<String> {
@@ -8,3 +8,13 @@ pub fn serialize<T: Serialize>(item: &T) -> Result<String> {
 pub fn deserialize<'a, T: Deserialize<'a>>(s: &'a str) -> Result<T> {
     serde_json::from_str(s)
 }
+
+/// Deserializes `null` as the default value of the field's type. Combine with
+/// `#[serde(default)]` to also accept a missing field.
+pub fn null_as_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
@@ -0,0 +1,77 @@
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::error::Error;
+
+/// A stream of audio chunks, yielded as soon as the API produces them.
+pub type AudioStream = BoxStream<'static, Result<Bytes, Error>>;
+
+/// Turns the body of a successful response into an `AudioStream`.
+pub fn audio_stream(response: Response) -> AudioStream {
+    response.bytes_stream().map_err(Error::Http).boxed()
+}
+
+/// Parses the body of a successful response as newline-delimited JSON, yielding each
+/// object as soon as its line is complete.
+pub fn json_lines<T>(response: Response) -> BoxStream<'static, Result<T, Error>>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let body = response.bytes_stream().map_err(Error::Http).boxed();
+
+    stream::try_unfold((body, BytesMut::new()), |(mut body, mut buffer)| async move {
+        loop {
+            if let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+                let line = buffer.split_to(end + 1);
+                let line = line[..end].trim_ascii();
+                if line.is_empty() {
+                    continue;
+                }
+                return Ok(Some((serde_json::from_slice(line)?, (body, buffer))));
+            }
+
+            match body.next().await {
+                Some(chunk) => buffer.extend_from_slice(&chunk?),
+                None if buffer.trim_ascii().is_empty() => return Ok(None),
+                None => {
+                    let item = serde_json::from_slice(buffer.trim_ascii())?;
+                    buffer.advance(buffer.len());
+                    return Ok(Some((item, (body, buffer))));
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+/// Writes every chunk of `stream` to `writer` as it arrives and returns the number of bytes written.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use elevenlabs_rust::utils::AudioStream;
+/// # async fn run(stream: AudioStream) -> Result<(), elevenlabs_rust::Error> {
+/// let mut file = tokio::fs::File::create("speech.mp3").await?;
+/// let written = elevenlabs_rust::utils::write_stream(stream, &mut file).await?;
+/// println!("Wrote {} bytes", written);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn write_stream<W>(mut stream: AudioStream, writer: &mut W) -> Result<u64, Error>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut written = 0;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        writer.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+    }
+
+    writer.flush().await?;
+    Ok(written)
+}
@@ -0,0 +1,246 @@
+/// The boundaries text is split at, from the most to the least preferred.
+#[derive(Clone, Copy)]
+enum Boundary {
+    Paragraph,
+    Sentence,
+    Word,
+}
+
+impl Boundary {
+    fn finer(self) -> Option<Boundary> {
+        match self {
+            Boundary::Paragraph => Some(Boundary::Sentence),
+            Boundary::Sentence => Some(Boundary::Word),
+            Boundary::Word => None,
+        }
+    }
+
+    fn split(self, text: &str) -> Vec<&str> {
+        match self {
+            Boundary::Paragraph => split_after(text, is_paragraph_end),
+            Boundary::Sentence => split_after(text, is_sentence_end),
+            Boundary::Word => text.split_inclusive(char::is_whitespace).collect(),
+        }
+    }
+}
+
+/// Splits `text` into chunks of at most `max_chars` characters.
+///
+/// Chunks end at paragraph breaks where possible, then at sentence ends, then between
+/// words. Only a single word longer than `max_chars` is cut in the middle.
+pub fn split_text(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for paragraph in Boundary::Paragraph.split(text) {
+        push_unit(paragraph, Boundary::Paragraph, max_chars, &mut chunks, &mut current);
+    }
+    flush(&mut chunks, &mut current);
+
+    chunks
+}
+
+fn push_unit(unit: &str, boundary: Boundary, max_chars: usize, chunks: &mut Vec<String>, current: &mut String) {
+    let unit_chars = unit.trim_end().chars().count();
+
+    if current.chars().count() + unit_chars <= max_chars {
+        current.push_str(unit);
+        return;
+    }
+
+    if unit_chars <= max_chars {
+        // Starting a new chunk here keeps the unit in one piece.
+        flush(chunks, current);
+        current.push_str(unit.trim_start());
+        return;
+    }
+
+    match boundary.finer() {
+        Some(finer) => {
+            for piece in finer.split(unit) {
+                push_unit(piece, finer, max_chars, chunks, current);
+            }
+        }
+        None => {
+            let chars: Vec<char> = unit.trim().chars().collect();
+            for piece in chars.chunks(max_chars) {
+                flush(chunks, current);
+                current.extend(piece);
+            }
+            current.push(' ');
+        }
+    }
+}
+
+fn flush(chunks: &mut Vec<String>, current: &mut String) {
+    let chunk = current.trim();
+    if !chunk.is_empty() {
+        chunks.push(chunk.to_string());
+    }
+    current.clear();
+}
+
+/// Splits `text` right after every position for which `is_end` returns the end of a separator.
+fn split_after(text: &str, is_end: fn(&str, usize) -> Option<usize>) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    for (index, _) in text.char_indices() {
+        if index < start {
+            continue;
+        }
+        if let Some(end) = is_end(text, index) {
+            pieces.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+
+    pieces
+}
+
+/// Detects a blank line at `index` and returns the end of the surrounding whitespace.
+fn is_paragraph_end(text: &str, index: usize) -> Option<usize> {
+    let rest = &text[index..];
+    if !rest.starts_with('\n') {
+        return None;
+    }
+
+    let after_newline = rest[1..].trim_start_matches([' ', '\t', '\r']);
+    if !after_newline.starts_with('\n') {
+        return None;
+    }
+
+    Some(text.len() - rest.trim_start().len())
+}
+
+/// Detects sentence-ending punctuation at `index` and returns the end of the trailing
+/// closing quotes, brackets and whitespace.
+fn is_sentence_end(text: &str, index: usize) -> Option<usize> {
+    let rest = &text[index..];
+    let first = rest.chars().next()?;
+
+    if matches!(first, '。' | '！' | '？') {
+        let after = rest[first.len_utf8()..].trim_start();
+        return Some(text.len() - after.len());
+    }
+    if !matches!(first, '.' | '!' | '?' | '…') {
+        return None;
+    }
+
+    let after_punctuation = rest[first.len_utf8()..].trim_start_matches(['.', '!', '?', '"', '\'', '”', '’', ')', ']']);
+    if after_punctuation.is_empty() || !after_punctuation.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    Some(text.len() - after_punctuation.trim_start().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_fits(chunks: &[String], max_chars: usize) {
+        for chunk in chunks {
+            assert!(
+                chunk.chars().count() <= max_chars,
+                "chunk of {} characters exceeds {}: {:?}",
+                chunk.chars().count(),
+                max_chars,
+                chunk
+            );
+        }
+    }
+
+    #[test]
+    fn short_text_is_one_chunk() {
+        assert_eq!(split_text("  Hello there.  ", 100), ["Hello there."]);
+        assert!(split_text("   ", 100).is_empty());
+    }
+
+    #[test]
+    fn splits_at_paragraphs_first() {
+        let text = "First paragraph. It has two sentences.\n\nSecond paragraph.\n \nThird.";
+        let chunks = split_text(text, 40);
+
+        assert_eq!(chunks, ["First paragraph. It has two sentences.", "Second paragraph.\n \nThird."]);
+    }
+
+    #[test]
+    fn falls_back_to_sentences() {
+        let text = "One sentence here. Another sentence! A question? \"Quoted end.\" Last one.";
+        let chunks = split_text(text, 30);
+
+        assert_eq!(
+            chunks,
+            ["One sentence here.", "Another sentence! A question?", "\"Quoted end.\" Last one."]
+        );
+        assert_fits(&chunks, 30);
+    }
+
+    #[test]
+    fn does_not_split_inside_numbers_or_abbreviations_without_space() {
+        let chunks = split_text("Version 1.5 costs $3.50 today. Buy now.", 31);
+
+        assert_eq!(chunks, ["Version 1.5 costs $3.50 today.", "Buy now."]);
+    }
+
+    #[test]
+    fn falls_back_to_words() {
+        let text = "a sentence without any punctuation that is far too long to fit";
+        let chunks = split_text(text, 20);
+
+        assert_eq!(chunks, ["a sentence without", "any punctuation that", "is far too long to", "fit"]);
+        assert_fits(&chunks, 20);
+    }
+
+    #[test]
+    fn hard_splits_words_longer_than_the_limit() {
+        let chunks = split_text("tiny Supercalifragilisticexpialidocious end", 10);
+
+        assert_eq!(chunks, ["tiny", "Supercalif", "ragilistic", "expialidoc", "ious end"]);
+        assert_fits(&chunks, 10);
+    }
+
+    #[test]
+    fn splits_at_cjk_sentence_punctuation() {
+        let text = "今日は晴れです。明日は雨でしょう！本当ですか？はい。";
+        let chunks = split_text(text, 12);
+
+        assert_eq!(chunks, ["今日は晴れです。", "明日は雨でしょう！", "本当ですか？はい。"]);
+        assert_fits(&chunks, 12);
+    }
+
+    #[test]
+    fn counts_characters_rather_than_bytes() {
+        let text = "ééééé ééééé";
+        assert_eq!(split_text(text, 11), [text]);
+        assert_eq!(split_text(text, 5), ["ééééé", "ééééé"]);
+    }
+
+    #[test]
+    fn no_chunk_exceeds_the_limit() {
+        let text = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed do eiusmod tempor!\n\n\
+                    Incididunt ut labore et dolore magna aliqua… Ut enim ad minim veniam? \
+                    Quis nostrud exercitation ullamcorper suscipit lobortis nisl ut aliquip.\n\n\
+                    これは日本語の文です。とても長い文章が続きます！\n\n\
+                    Pneumonoultramicroscopicsilicovolcanoconiosis is a long word.";
+
+        for max_chars in [1, 2, 5, 7, 13, 20, 33, 64, 100, 1000] {
+            let chunks = split_text(text, max_chars);
+            assert_fits(&chunks, max_chars);
+
+            let original: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+            let joined: String = chunks.concat().chars().filter(|c| !c.is_whitespace()).collect();
+            assert_eq!(joined, original, "text was lost with max_chars = {}", max_chars);
+        }
+    }
+
+    #[test]
+    fn zero_limit_is_treated_as_one() {
+        assert_eq!(split_text("ab", 0), ["a", "b"]);
+    }
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use reqwest::multipart::Part;
+use reqwest::Body;
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+
+use crate::error::Error;
+
+type BoxedReader = Pin<Box<dyn AsyncRead + Send + Sync>>;
+
+/// A file to upload with a multipart request: a path on disk, bytes already in memory,
+/// or an async reader of known length.
+pub struct FileUpload {
+    source: Source,
+    file_name: Option<String>,
+    mime_type: Option<String>,
+}
+
+enum Source {
+    Path(PathBuf),
+    Bytes(Bytes),
+    Reader { reader: BoxedReader, length: u64 },
+}
+
+impl FileUpload {
+    /// Uploads the file at `path`, named after the file.
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        FileUpload {
+            source: Source::Path(path.into()),
+            file_name: None,
+            mime_type: None,
+        }
+    }
+
+    /// Uploads bytes already held in memory.
+    pub fn from_bytes(data: impl Into<Bytes>, file_name: impl Into<String>) -> Self {
+        FileUpload {
+            source: Source::Bytes(data.into()),
+            file_name: Some(file_name.into()),
+            mime_type: None,
+        }
+    }
+
+    /// Streams `length` bytes from `reader` without buffering them.
+    ///
+    /// A reader can only be read once, so requests uploading it are never retried.
+    pub fn from_reader<R>(reader: R, length: u64, file_name: impl Into<String>) -> Self
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        FileUpload {
+            source: Source::Reader {
+                reader: Box::pin(reader),
+                length,
+            },
+            file_name: Some(file_name.into()),
+            mime_type: None,
+        }
+    }
+
+    /// Overrides the file name sent with the upload.
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Sets the MIME type of the upload, e.g. `audio/mpeg`. Without it, the type is detected
+    /// from the file extension, or from the first bytes of data held in memory.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    /// Returns the file name sent with the upload.
+    pub fn file_name(&self) -> String {
+        match (&self.file_name, &self.source) {
+            (Some(file_name), _) => file_name.clone(),
+            (None, Source::Path(path)) => file_name_of(path),
+            (None, _) => String::new(),
+        }
+    }
+
+    /// Returns the size of the upload in bytes, without reading files from disk.
+    pub async fn size(&self) -> Result<u64, Error> {
+        match &self.source {
+            Source::Path(path) => Ok(tokio::fs::metadata(path).await?.len()),
+            Source::Bytes(data) => Ok(data.len() as u64),
+            Source::Reader { length, .. } => Ok(*length),
+        }
+    }
+
+    /// Loads files from disk so the upload can be attached to one or more requests.
+    pub async fn prepare(self) -> Result<PreparedUpload, Error> {
+        let file_name = self.file_name();
+        let source = match self.source {
+            Source::Path(path) => PreparedSource::Bytes(Bytes::from(tokio::fs::read(&path).await?)),
+            Source::Bytes(data) => PreparedSource::Bytes(data),
+            Source::Reader { reader, length } => PreparedSource::Reader {
+                reader: Mutex::new(Some(reader)),
+                length,
+            },
+        };
+
+        let mime_type = self.mime_type.or_else(|| {
+            let head = match &source {
+                PreparedSource::Bytes(data) => Some(&data[..]),
+                PreparedSource::Reader { .. } => None,
+            };
+            detect_mime_type(&file_name, head).map(str::to_string)
+        });
+
+        Ok(PreparedUpload {
+            source,
+            file_name,
+            mime_type,
+        })
+    }
+}
+
+impl fmt::Debug for FileUpload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let source = match &self.source {
+            Source::Path(path) => format!("Path({})", path.display()),
+            Source::Bytes(data) => format!("Bytes({} bytes)", data.len()),
+            Source::Reader { length, .. } => format!("Reader({} bytes)", length),
+        };
+        f.debug_struct("FileUpload")
+            .field("source", &source)
+            .field("file_name", &self.file_name)
+            .field("mime_type", &self.mime_type)
+            .finish()
+    }
+}
+
+impl From<&str> for FileUpload {
+    fn from(path: &str) -> Self {
+        FileUpload::from_path(path)
+    }
+}
+
+impl From<PathBuf> for FileUpload {
+    fn from(path: PathBuf) -> Self {
+        FileUpload::from_path(path)
+    }
+}
+
+/// An upload ready to be turned into multipart parts, once per attempt.
+pub struct PreparedUpload {
+    source: PreparedSource,
+    file_name: String,
+    mime_type: Option<String>,
+}
+
+enum PreparedSource {
+    Bytes(Bytes),
+    Reader {
+        reader: Mutex<Option<BoxedReader>>,
+        length: u64,
+    },
+}
+
+impl PreparedUpload {
+    /// Returns the file name sent with the upload.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Returns the MIME type sent with the upload, if it was set or detected.
+    pub fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_deref()
+    }
+
+    /// Whether the upload can be sent more than once, and so be retried.
+    pub fn is_replayable(&self) -> bool {
+        matches!(self.source, PreparedSource::Bytes(_))
+    }
+
+    /// Builds a multipart part for the upload.
+    pub fn to_part(&self) -> Result<Part, Error> {
+        let part = match &self.source {
+            PreparedSource::Bytes(data) => Part::stream_with_length(Body::from(data.clone()), data.len() as u64),
+            PreparedSource::Reader { reader, length } => {
+                let reader = reader
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .take()
+                    .ok_or_else(|| Error::InvalidRequest("a streamed upload can only be sent once".to_string()))?;
+                Part::stream_with_length(Body::wrap_stream(ReaderStream::new(reader)), *length)
+            }
+        };
+
+        let part = part.file_name(self.file_name.clone());
+        match &self.mime_type {
+            Some(mime_type) => Ok(part.mime_str(mime_type)?),
+            None => Ok(part),
+        }
+    }
+}
+
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string_lossy().into_owned())
+}
+
+/// Guesses the MIME type of a file from its extension, then from its first bytes.
+pub fn detect_mime_type(file_name: &str, head: Option<&[u8]>) -> Option<&'static str> {
+    let extension = Path::new(file_name)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+    let by_extension = match extension.as_deref() {
+        Some("mp3") => Some("audio/mpeg"),
+        Some("wav") => Some("audio/wav"),
+        Some("flac") => Some("audio/flac"),
+        Some("ogg") | Some("oga") => Some("audio/ogg"),
+        Some("opus") => Some("audio/opus"),
+        Some("m4a") | Some("mp4") => Some("audio/mp4"),
+        Some("aac") => Some("audio/aac"),
+        Some("webm") => Some("audio/webm"),
+        Some("pls") => Some("application/pls+xml"),
+        _ => None,
+    };
+
+    by_extension.or_else(|| head.and_then(sniff_mime_type))
+}
+
+/// Recognizes the common audio containers by their signature.
+fn sniff_mime_type(head: &[u8]) -> Option<&'static str> {
+    if head.starts_with(b"ID3") || (head.len() >= 2 && head[0] == 0xFF && head[1] & 0xE0 == 0xE0 && head[1] & 0x06 != 0) {
+        Some("audio/mpeg")
+    } else if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WAVE") {
+        Some("audio/wav")
+    } else if head.starts_with(b"fLaC") {
+        Some("audio/flac")
+    } else if head.starts_with(b"OggS") {
+        Some("audio/ogg")
+    } else if head.get(4..8) == Some(b"ftyp") {
+        Some("audio/mp4")
+    } else if head.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        Some("audio/webm")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_name_and_size_do_not_read_the_file() {
+        let path = std::env::temp_dir().join(format!("upload-size-{}.wav", std::process::id()));
+        tokio::fs::write(&path, vec![0u8; 42]).await.unwrap();
+
+        let upload = FileUpload::from_path(&path);
+        assert_eq!(upload.file_name(), path.file_name().unwrap().to_string_lossy());
+        assert_eq!(upload.size().await.unwrap(), 42);
+        assert_eq!(upload.with_file_name("take.wav").file_name(), "take.wav");
+
+        let upload = FileUpload::from_reader(tokio::io::empty(), 7, "stream.mp3");
+        assert_eq!((upload.file_name(), upload.size().await.unwrap()), ("stream.mp3".to_string(), 7));
+        assert_eq!(FileUpload::from_bytes(vec![1, 2, 3], "a.mp3").size().await.unwrap(), 3);
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[test]
+    fn mime_type_from_extension() {
+        let cases = [
+            ("take.mp3", Some("audio/mpeg")),
+            ("TAKE.MP3", Some("audio/mpeg")),
+            ("take.Wav", Some("audio/wav")),
+            ("take.flac", Some("audio/flac")),
+            ("take.ogg", Some("audio/ogg")),
+            ("take.oga", Some("audio/ogg")),
+            ("take.OPUS", Some("audio/opus")),
+            ("take.m4a", Some("audio/mp4")),
+            ("take.mp4", Some("audio/mp4")),
+            ("take.aac", Some("audio/aac")),
+            ("take.webm", Some("audio/webm")),
+            ("lexicon.PLS", Some("application/pls+xml")),
+            ("take.txt", None),
+            ("take", None),
+        ];
+        for (file_name, expected) in cases {
+            assert_eq!(detect_mime_type(file_name, None), expected, "{}", file_name);
+        }
+        // The extension wins over the content.
+        assert_eq!(detect_mime_type("take.wav", Some(b"ID3\x04")), Some("audio/wav"));
+    }
+
+    #[test]
+    fn mime_type_from_content() {
+        let cases: [(&[u8], Option<&str>); 11] = [
+            (b"ID3\x04\x00\x00", Some("audio/mpeg")),
+            (&[0xFF, 0xFB, 0x90, 0x64], Some("audio/mpeg")),
+            (&[0xFF, 0xF3, 0x48, 0xC4], Some("audio/mpeg")),
+            // The frame sync with a reserved layer is not MPEG audio.
+            (&[0xFF, 0xE0, 0x00, 0x00], None),
+            (b"RIFF\x24\x08\x00\x00WAVEfmt ", Some("audio/wav")),
+            (b"RIFF\x24\x08\x00\x00AVI LIST", None),
+            (b"fLaC\x00\x00\x00\x22", Some("audio/flac")),
+            (b"OggS\x00\x02\x00\x00", Some("audio/ogg")),
+            (b"\x00\x00\x00\x20ftypM4A ", Some("audio/mp4")),
+            (&[0x1A, 0x45, 0xDF, 0xA3, 0x9F, 0x42, 0x86, 0x81], Some("audio/webm")),
+            (b"", None),
+        ];
+        for (head, expected) in cases {
+            assert_eq!(detect_mime_type("upload", Some(head)), expected, "{:?}", head);
+        }
+    }
+
+    #[tokio::test]
+    async fn explicit_mime_type_overrides_detection() {
+        let upload = FileUpload::from_bytes(&b"ID3\x04\x00"[..], "take.wav").prepare().await.unwrap();
+        assert_eq!(upload.mime_type(), Some("audio/wav"));
+
+        let upload = FileUpload::from_bytes(&b"ID3\x04\x00"[..], "take").prepare().await.unwrap();
+        assert_eq!(upload.mime_type(), Some("audio/mpeg"));
+
+        let upload = FileUpload::from_bytes(&b"ID3\x04\x00"[..], "take.wav")
+            .with_mime_type("audio/x-custom")
+            .prepare()
+            .await
+            .unwrap();
+        assert_eq!(upload.mime_type(), Some("audio/x-custom"));
+
+        let upload = FileUpload::from_reader(tokio::io::empty(), 0, "stream").prepare().await.unwrap();
+        assert_eq!(upload.mime_type(), None);
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytes::Bytes;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::api::utils::{create_request, send_with_retry};
+use crate::api::voices::Voice;
+use crate::config::Config;
+use crate::error::{parse_request_id, Error};
+use crate::models::{Audio, OutputFormat};
+
+/// A value accepted by one of the voice generation parameters, e.g. the `british` accent.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GenerationOption {
+    pub name: String,
+    pub code: String,
+}
+
+/// The values and limits accepted when generating a voice from attributes.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GenerationParameters {
+    pub genders: Vec<GenerationOption>,
+    pub accents: Vec<GenerationOption>,
+    pub ages: Vec<GenerationOption>,
+    pub minimum_characters: u32,
+    pub maximum_characters: u32,
+    pub minimum_accent_strength: f32,
+    pub maximum_accent_strength: f32,
+}
+
+/// The request payload for generating a voice from attributes.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GenerateVoiceRequest {
+    /// A gender code from `GenerationParameters::genders`.
+    pub gender: String,
+    /// An age code from `GenerationParameters::ages`.
+    pub age: String,
+    /// An accent code from `GenerationParameters::accents`.
+    pub accent: String,
+    /// How strongly the accent is applied, within the range of `GenerationParameters`.
+    pub accent_strength: f32,
+    /// The text the preview speaks.
+    pub text: String,
+    /// The format of the preview audio, sent as the `output_format` query parameter.
+    #[serde(skip)]
+    pub output_format: Option<OutputFormat>,
+}
+
+/// The request payload for designing voices from a free-form description.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct VoiceDesignRequest {
+    /// A description of the voice, e.g. "A gravelly old sea captain with a Scottish accent".
+    pub voice_description: String,
+    /// The text the previews speak. Required unless `auto_generate_text` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Lets the API write a text that suits the description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_generate_text: Option<bool>,
+    /// The format of the preview audio, sent as the `output_format` query parameter.
+    #[serde(skip)]
+    pub output_format: Option<OutputFormat>,
+}
+
+/// The request payload for saving a preview as a permanent voice.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct CreateVoiceFromPreviewRequest {
+    pub voice_name: String,
+    pub voice_description: String,
+    /// The ID of the chosen preview, from `VoicePreview::generated_voice_id`.
+    pub generated_voice_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<HashMap<String, String>>,
+}
+
+/// A generated voice that has not been saved yet, with a sample of its audio.
+#[derive(Debug, Clone)]
+pub struct VoicePreview {
+    /// Identifies the preview when saving it with `create_voice_from_preview`.
+    pub generated_voice_id: String,
+    pub audio: Audio,
+    pub duration_secs: Option<f64>,
+}
+
+/// The response body of the voice design endpoint.
+#[derive(Deserialize)]
+struct VoiceDesignResponse {
+    previews: Vec<VoiceDesignPreview>,
+}
+
+#[derive(Deserialize)]
+struct VoiceDesignPreview {
+    audio_base_64: String,
+    generated_voice_id: String,
+    duration_secs: Option<f64>,
+}
+
+/// Client for interacting with the ElevenLabs Voice Generation API.
+#[derive(Clone)]
+pub struct VoiceGenerationClient {
+    client: Client,
+    config: Arc<Config>,
+}
+
+impl VoiceGenerationClient {
+    /// Creates a new `VoiceGenerationClient` using the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A `Config` instance containing the necessary configuration.
+    pub fn new(config: Config) -> Self {
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `VoiceGenerationClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
+        VoiceGenerationClient { client, config }
+    }
+
+    /// Fetches the genders, ages and accents a voice can be generated with, and the limits
+    /// of the accent strength and preview text.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `GenerationParameters`, or `Error` on failure.
+    pub async fn get_generation_parameters(&self) -> Result<GenerationParameters, Error> {
+        let url = format!("{}/v1/voice-generation/generate-voice/parameters", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(response.json::<GenerationParameters>().await?)
+    }
+
+    /// Generates a random voice with the given gender, age and accent.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The attributes of the voice and the text of the preview.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `VoicePreview`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voice_generation::{GenerateVoiceRequest, VoiceGenerationClient}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let generation_client = VoiceGenerationClient::new(config);
+    /// let request = GenerateVoiceRequest {
+    ///     gender: "female".to_string(),
+    ///     age: "middle_aged".to_string(),
+    ///     accent: "british".to_string(),
+    ///     accent_strength: 1.2,
+    ///     text: "Welcome, traveller. The road ahead is long, and the night is closing in fast. \
+    ///            Rest here a while, and tell me what brings you so far from home.".to_string(),
+    ///     output_format: None,
+    /// };
+    /// let preview = generation_client.generate_voice(&request).await?;
+    /// println!("Generated voice {}", preview.generated_voice_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_voice(&self, request: &GenerateVoiceRequest) -> Result<VoicePreview, Error> {
+        let url = format!("{}/v1/voice-generation/generate-voice", &self.config.api_url);
+        let format = request.output_format.unwrap_or_default();
+
+        let response = send_with_retry(&self.config.retry_policy, false, || {
+            Ok(self.client.post(&url)
+                .header("Accept", format.mime_type())
+                .header("xi-api-key", &self.config.api_key)
+                .query(&[("output_format", format.as_str())])
+                .json(request))
+        })
+        .await?;
+
+        let generated_voice_id = response
+            .headers()
+            .get("generated_voice_id")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| Error::Protocol("response is missing the generated_voice_id header".to_string()))?;
+        let request_id = parse_request_id(response.headers());
+
+        Ok(VoicePreview {
+            generated_voice_id,
+            audio: Audio {
+                data: response.bytes().await?,
+                format,
+                request_id,
+            },
+            duration_secs: None,
+        })
+    }
+
+    /// Designs voices from a free-form description and returns a preview of each.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The description of the voice and the text of the previews.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `VoicePreview`s, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voice_generation::{VoiceDesignRequest, VoiceGenerationClient}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let generation_client = VoiceGenerationClient::new(config);
+    /// let request = VoiceDesignRequest {
+    ///     voice_description: "A gravelly old sea captain with a thick Scottish accent".to_string(),
+    ///     auto_generate_text: Some(true),
+    ///     ..Default::default()
+    /// };
+    /// for preview in generation_client.design_voice(&request).await? {
+    ///     std::fs::write(format!("{}.mp3", preview.generated_voice_id), &preview.audio.data)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn design_voice(&self, request: &VoiceDesignRequest) -> Result<Vec<VoicePreview>, Error> {
+        let url = format!("{}/v1/text-to-voice/create-previews", &self.config.api_url);
+        let format = request.output_format.unwrap_or_default();
+
+        let response = send_with_retry(&self.config.retry_policy, false, || {
+            Ok(self.client.post(&url)
+                .header("Accept", "application/json")
+                .header("xi-api-key", &self.config.api_key)
+                .query(&[("output_format", format.as_str())])
+                .json(request))
+        })
+        .await?;
+        let request_id = parse_request_id(response.headers());
+
+        response
+            .json::<VoiceDesignResponse>()
+            .await?
+            .previews
+            .into_iter()
+            .map(|preview| {
+                let audio = BASE64
+                    .decode(preview.audio_base_64)
+                    .map_err(|err| Error::Protocol(format!("invalid preview audio: {}", err)))?;
+                Ok(VoicePreview {
+                    generated_voice_id: preview.generated_voice_id,
+                    audio: Audio {
+                        data: Bytes::from(audio),
+                        format,
+                        request_id: request_id.clone(),
+                    },
+                    duration_secs: preview.duration_secs,
+                })
+            })
+            .collect()
+    }
+
+    /// Saves a preview as a permanent voice in the account's voice library.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The chosen preview and the name and description of the new voice.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the created `Voice`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voice_generation::{CreateVoiceFromPreviewRequest, VoiceGenerationClient}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let generation_client = VoiceGenerationClient::new(config);
+    /// let request = CreateVoiceFromPreviewRequest {
+    ///     voice_name: "Captain Angus".to_string(),
+    ///     voice_description: "A gravelly old sea captain with a thick Scottish accent".to_string(),
+    ///     generated_voice_id: "generated_voice_id".to_string(),
+    ///     labels: None,
+    /// };
+    /// let voice = generation_client.create_voice_from_preview(&request).await?;
+    /// println!("Saved as {}", voice.voice_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_voice_from_preview(&self, request: &CreateVoiceFromPreviewRequest) -> Result<Voice, Error> {
+        let url = format!("{}/v1/text-to-voice/create-voice-from-preview", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, false, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(request))
+        })
+        .await?;
+
+        Ok(response.json::<Voice>().await?)
+    }
+}
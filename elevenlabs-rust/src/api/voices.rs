@@ -1,32 +1,372 @@
-use reqwest::{Client, Error as ReqwestError};
-use serde::Deserialize;
-// use my own crate's config
-use crate::config_loader::Config;
-use crate::utils::{create_request, UtilsError};
-
-/// Response structure for metadata about a specific voice.
-#[derive(Deserialize, Debug)]
-pub struct VoiceMetadata {
-    // Define the fields based on the API's response format for a voice.
-    // Example fields; adjust according to actual API response.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::stream::BoxStream;
+use reqwest::{multipart, Client};
+use serde::{Deserialize, Serialize};
+
+use crate::api::utils::{
+    create_request, null_as_default, paginate, send_with_retry, FileUpload, PreparedUpload, RetryPolicy,
+};
+use crate::config::Config;
+use crate::error::Error;
+
+pub use crate::models::VoiceSettings;
+
+/// The category of a voice.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceCategory {
+    Generated,
+    Cloned,
+    Premade,
+    Professional,
+    Famous,
+    HighQuality,
+    /// A category this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// An audio sample a voice was cloned from.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Sample {
+    pub sample_id: String,
+    pub file_name: Option<String>,
+    pub mime_type: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub hash: Option<String>,
+    pub duration_secs: Option<f64>,
+    /// Whether background noise was removed from the sample.
+    pub remove_background_noise: Option<bool>,
+}
+
+/// The progress of separating the speakers of a sample.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeakerSeparationStatus {
+    NotStarted,
+    Pending,
+    Completed,
+    Failed,
+    /// A status this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A stretch of a sample during which one speaker talks.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Utterance {
+    /// The start of the utterance, in seconds.
+    pub start: f64,
+    /// The end of the utterance, in seconds.
+    pub end: f64,
+}
+
+/// A speaker detected in a sample.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Speaker {
+    pub speaker_id: String,
+    pub duration_secs: f64,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub utterances: Vec<Utterance>,
+}
+
+/// The speakers detected in a sample.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SpeakerSeparation {
+    pub voice_id: String,
+    pub sample_id: String,
+    pub status: SpeakerSeparationStatus,
+    /// The detected speakers, keyed by speaker ID. Empty until the separation is completed.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub speakers: HashMap<String, Speaker>,
+    /// The speakers whose audio is used to train the voice.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub selected_speaker_ids: Vec<String>,
+}
+
+/// The isolated audio of one speaker of a sample.
+#[derive(Debug, Clone)]
+pub struct SpeakerAudio {
+    pub audio: Bytes,
+    /// The MIME type of `audio`, e.g. `audio/mpeg`.
+    pub media_type: String,
+    pub duration_secs: f64,
+}
+
+/// The response body of the speaker audio endpoint.
+#[derive(Deserialize)]
+struct SpeakerAudioResponse {
+    audio_base_64: String,
+    media_type: String,
+    duration_secs: f64,
+}
+
+/// The state of fine-tuning a voice for one model.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FineTuningState {
+    NotStarted,
+    Queued,
+    FineTuning,
+    FineTuned,
+    Failed,
+    Delayed,
+    /// A state this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The fine-tuning (professional cloning) status of a voice.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FineTuning {
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub is_allowed_to_fine_tune: bool,
+    /// The fine-tuning state per model ID.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub state: HashMap<String, FineTuningState>,
+    /// The fine-tuning progress per model ID, from `0.0` to `1.0`.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub progress: HashMap<String, f64>,
+    /// The latest status message per model ID.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub message: HashMap<String, String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub verification_failures: Vec<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub verification_attempts_count: u32,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub manual_verification_requested: bool,
+    pub language: Option<String>,
+}
+
+/// How a voice is shared in the voice library.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VoiceSharing {
+    /// `enabled`, `disabled`, `copied` or `copied_disabled`.
+    pub status: Option<String>,
+    pub public_owner_id: Option<String>,
+    pub original_voice_id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub labels: HashMap<String, String>,
+    pub category: Option<String>,
+    pub review_status: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub enabled_in_library: bool,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub liked_by_count: u64,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub cloned_by_count: u64,
+    pub date_unix: Option<i64>,
+}
+
+/// A voice, as returned by the voice listing and lookup endpoints.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Voice {
+    pub voice_id: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub name: String,
+    pub category: Option<VoiceCategory>,
+    /// Free-form labels such as `accent`, `age`, `gender` and `use_case`.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub labels: HashMap<String, String>,
+    pub description: Option<String>,
+    pub preview_url: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub samples: Vec<Sample>,
+    /// Only present when the voice was fetched with its settings.
+    pub settings: Option<VoiceSettings>,
+    pub sharing: Option<VoiceSharing>,
+    pub fine_tuning: Option<FineTuning>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub high_quality_base_model_ids: Vec<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub available_for_tiers: Vec<String>,
+    pub is_owner: Option<bool>,
+    pub created_at_unix: Option<i64>,
+}
+
+/// The response body of the voice listing endpoint.
+#[derive(Deserialize)]
+struct VoicesResponse {
+    voices: Vec<Voice>,
+}
+
+/// The fields the v2 voice search can be sorted by.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceSort {
+    CreatedAtUnix,
+    Name,
+}
+
+/// The direction of a sort.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// The owners of the voices the v2 voice search returns.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum VoiceType {
+    Personal,
+    Community,
+    Default,
+    Workspace,
+    NonDefault,
+    Saved,
+}
+
+/// Filters and pagination of the v2 voice search.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct VoiceSearchParams {
+    /// The number of voices per page, at most 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    /// The token of the page to fetch, from `VoicePage::next_page_token`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    /// Matches the name, description, labels and category of the voice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<VoiceSort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_direction: Option<SortDirection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice_type: Option<VoiceType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<VoiceCategory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_total_count: Option<bool>,
+}
+
+/// A page of the v2 voice search.
+#[derive(Deserialize, Debug, Clone)]
+pub struct VoicePage {
+    pub voices: Vec<Voice>,
+    pub has_more: bool,
+    pub total_count: Option<u64>,
+    pub next_page_token: Option<String>,
+}
+
+/// A voice of the public voice library.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SharedVoice {
+    /// The ID of the user who shared the voice, needed to add it to an account.
+    pub public_owner_id: String,
     pub voice_id: String,
-    // ... other metadata fields ...
-    // If with_settings is true, there might be additional settings fields.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub name: String,
+    pub category: Option<VoiceCategory>,
+    pub gender: Option<String>,
+    pub age: Option<String>,
+    pub accent: Option<String>,
+    pub language: Option<String>,
+    pub use_case: Option<String>,
+    pub descriptive: Option<String>,
+    pub description: Option<String>,
+    pub preview_url: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub featured: bool,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub free_users_allowed: bool,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub cloned_by_count: u64,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub usage_character_count_1y: u64,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub usage_character_count_7d: u64,
+    pub date_unix: Option<i64>,
 }
 
-/// Represents the voice settings returned by the API.
-#[derive(Deserialize, Debug)]
-pub struct VoiceSettings {
-    pub stability: f32,
-    pub similarity_boost: f32,
-    pub style: Option<f32>,
-    pub use_speaker_boost: Option<bool>,
+/// Filters and pagination of the shared voice library.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct SharedVoiceSearchParams {
+    /// The number of voices per page, at most 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    /// The page to fetch, starting at `0`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// Matches the name, description and labels of the voice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<VoiceCategory>,
+    /// E.g. `female`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gender: Option<String>,
+    /// E.g. `young`, `middle_aged` or `old`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<String>,
+    /// E.g. `british`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+    /// An ISO 639-1 code, e.g. `en`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Matches voices suited to any of these use cases, e.g. `narrative_story`.
+    #[serde(skip)]
+    pub use_cases: Vec<String>,
+    /// Only returns voices featured by ElevenLabs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub featured: Option<bool>,
+}
+
+/// A page of the shared voice library.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SharedVoicePage {
+    pub voices: Vec<SharedVoice>,
+    pub has_more: bool,
+}
+
+/// The request payload for adding a shared voice to the account.
+#[derive(Serialize)]
+struct AddSharedVoiceRequest<'a> {
+    new_name: &'a str,
+}
+
+/// Options of [`VoicesClient::add_voice`] and [`VoicesClient::edit_voice`].
+#[derive(Debug, Default, Clone)]
+pub struct VoiceCloneOptions {
+    pub description: Option<String>,
+    /// Labels describing the voice, e.g. `accent: "british"`.
+    pub labels: Option<HashMap<String, String>>,
+    /// Removes background noise from the samples before cloning. Samples without noise may
+    /// sound worse with it.
+    pub remove_background_noise: Option<bool>,
+}
+
+/// The response body of the add voice endpoint.
+#[derive(Deserialize)]
+struct AddVoiceResponse {
+    voice_id: String,
+}
+
+/// A voice listing kept by [`VoicesClient::with_listing_cache`].
+struct CachedListing {
+    voices: Arc<Vec<Voice>>,
+    fetched_at: Instant,
 }
 
 /// Client for interacting with the ElevenLabs Voices API.
+#[derive(Clone)]
 pub struct VoicesClient {
     client: Client,
-    config: Config,
+    config: Arc<Config>,
+    listing_ttl: Option<Duration>,
+    listing: Arc<Mutex<Option<CachedListing>>>,
 }
 
 impl VoicesClient {
@@ -36,10 +376,89 @@ impl VoicesClient {
     ///
     /// * `config` - A `Config` instance containing the necessary configuration.
     pub fn new(config: Config) -> Self {
+        Self::from_parts(Client::new(), Arc::new(config))
+    }
+
+    /// Creates a new `VoicesClient` that shares an existing HTTP client and configuration.
+    pub(crate) fn from_parts(client: Client, config: Arc<Config>) -> Self {
         VoicesClient {
-            client: Client::new(),
+            client,
             config,
+            listing_ttl: None,
+            listing: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Makes [`find_voices`](Self::find_voices) reuse the voice listing for `ttl` before
+    /// fetching it again. Clones of the returned client share the cached listing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use elevenlabs_rust::{Config, ElevenLabsClient};
+    /// let client = ElevenLabsClient::new(Config::new("your_api_key", "https://api.elevenlabs.io"));
+    /// let voices = client.voices().with_listing_cache(Duration::from_secs(10 * 60));
+    /// ```
+    pub fn with_listing_cache(mut self, ttl: Duration) -> Self {
+        self.listing_ttl = Some(ttl);
+        self
+    }
+
+    /// Forgets the cached voice listing, so it is fetched again on next use.
+    pub fn clear_listing_cache(&self) {
+        *self.listing.lock().unwrap() = None;
+    }
+
+    /// Starts a query that picks voices by category and labels.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::{VoiceCategory, VoicesClient}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// let voices = voices_client
+    ///     .find_voices()
+    ///     .gender("female")
+    ///     .accent("british")
+    ///     .age("young")
+    ///     .use_case("narration")
+    ///     .category(VoiceCategory::Premade)
+    ///     .fetch()
+    ///     .await?;
+    /// if let Some(voice) = voices.first() {
+    ///     println!("Best match: {} ({})", voice.name, voice.voice_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_voices(&self) -> VoiceQuery<'_> {
+        VoiceQuery {
+            client: self,
+            category: None,
+            labels: Vec::new(),
+            limit: None,
+        }
+    }
+
+    /// Returns the voice listing, from the cache while it is fresh if caching is enabled.
+    async fn voice_listing(&self) -> Result<Arc<Vec<Voice>>, Error> {
+        if let Some(ttl) = self.listing_ttl {
+            if let Some(cached) = self.listing.lock().unwrap().as_ref() {
+                if cached.fetched_at.elapsed() < ttl {
+                    return Ok(cached.voices.clone());
+                }
+            }
         }
+
+        let voices = Arc::new(self.list_voices().await?);
+        *self.listing.lock().unwrap() = Some(CachedListing {
+            voices: voices.clone(),
+            fetched_at: Instant::now(),
+        });
+        Ok(voices)
     }
 
     /// Fetches metadata about a specific voice from the ElevenLabs API.
@@ -51,11 +470,12 @@ impl VoicesClient {
     ///
     /// # Returns
     ///
-    /// A `Result` which on success contains `VoiceMetadata`, or `UtilsError` on failure.
+    /// A `Result` which on success contains the `Voice`, or `Error` on failure.
     ///
     /// # Examples
     ///
     /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::VoicesClient};
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
     /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
     /// let voices_client = VoicesClient::new(config);
@@ -64,22 +484,231 @@ impl VoicesClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_voice_metadata(&self, voice_id: &str, with_settings: bool) -> Result<VoiceMetadata, UtilsError> {
+    pub async fn get_voice_metadata(&self, voice_id: &str, with_settings: bool) -> Result<Voice, Error> {
         let url = format!("{}/v1/voices/{}", &self.config.api_url, voice_id);
 
-        let response = create_request(&self.client, reqwest::Method::GET, &url)
-            .header("xi-api-key", &self.config.api_key)
-            .query(&[("with_settings", with_settings)])
-            .send()
-            .await
-            .map_err(UtilsError::Http)?;
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .query(&[("with_settings", with_settings)]))
+        })
+        .await?;
 
-        if response.status().is_success() {
-            response.json::<VoiceMetadata>().await.map_err(UtilsError::Http)
-        } else {
-            let error_msg = format!("🚨 Failed to get voice metadata: HTTP {}", response.status());
-            Err(UtilsError::Custom(error_msg))
-        }
+        Ok(response.json::<Voice>().await?)
+    }
+
+    /// Lists every voice available to the account.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which on success contains the `Voice`s, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::VoicesClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// for voice in voices_client.list_voices().await? {
+    ///     println!("{}: {}", voice.voice_id, voice.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_voices(&self) -> Result<Vec<Voice>, Error> {
+        let url = format!("{}/v1/voices", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(response.json::<VoicesResponse>().await?.voices)
+    }
+
+    /// Fetches one page of the v2 voice search.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The filters, sort order and page to fetch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which on success contains the `VoicePage`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::{VoiceCategory, VoiceSearchParams, VoicesClient}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// let params = VoiceSearchParams {
+    ///     search: Some("narrator".to_string()),
+    ///     category: Some(VoiceCategory::Premade),
+    ///     ..Default::default()
+    /// };
+    /// let page = voices_client.search_voices(&params).await?;
+    /// println!("{} voices, more: {}", page.voices.len(), page.has_more);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_voices(&self, params: &VoiceSearchParams) -> Result<VoicePage, Error> {
+        let url = format!("{}/v2/voices", &self.config.api_url);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .query(params))
+        })
+        .await?;
+
+        Ok(response.json::<VoicePage>().await?)
+    }
+
+    /// Streams every voice matching `params`, fetching further pages of the v2 voice search
+    /// as the stream is consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::{VoiceSearchParams, VoicesClient}};
+    /// # use futures_util::TryStreamExt;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// let mut voices = voices_client.search_voices_stream(VoiceSearchParams::default());
+    /// while let Some(voice) = voices.try_next().await? {
+    ///     println!("{}: {}", voice.voice_id, voice.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_voices_stream(&self, params: VoiceSearchParams) -> BoxStream<'static, Result<Voice, Error>> {
+        let client = self.clone();
+
+        paginate(move |next_page_token: Option<String>| {
+            let client = client.clone();
+            let params = VoiceSearchParams {
+                next_page_token: next_page_token.or_else(|| params.next_page_token.clone()),
+                ..params.clone()
+            };
+            async move {
+                let page = client.search_voices(&params).await?;
+                let next = page.next_page_token.filter(|_| page.has_more);
+                Ok((page.voices, next))
+            }
+        })
+    }
+
+    /// Fetches a single page of the public voice library.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The filters and the page to fetch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `SharedVoicePage`, or `Error` on failure.
+    pub async fn search_shared_voices(&self, params: &SharedVoiceSearchParams) -> Result<SharedVoicePage, Error> {
+        let url = format!("{}/v1/shared-voices", &self.config.api_url);
+        let use_cases: Vec<(&str, &str)> = params.use_cases.iter().map(|use_case| ("use_cases", use_case.as_str())).collect();
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .query(params)
+                .query(&use_cases))
+        })
+        .await?;
+
+        Ok(response.json::<SharedVoicePage>().await?)
+    }
+
+    /// Streams every voice of the public voice library matching `params`, fetching further
+    /// pages as the stream is consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::{SharedVoiceSearchParams, VoicesClient}};
+    /// # use futures_util::TryStreamExt;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// let params = SharedVoiceSearchParams {
+    ///     gender: Some("female".to_string()),
+    ///     accent: Some("british".to_string()),
+    ///     use_cases: vec!["narrative_story".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// let mut voices = voices_client.search_shared_voices_stream(params);
+    /// while let Some(voice) = voices.try_next().await? {
+    ///     println!("{} by {}: {}", voice.voice_id, voice.public_owner_id, voice.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_shared_voices_stream(
+        &self,
+        params: SharedVoiceSearchParams,
+    ) -> BoxStream<'static, Result<SharedVoice, Error>> {
+        let client = self.clone();
+
+        paginate(move |page: Option<u32>| {
+            let client = client.clone();
+            let params = SharedVoiceSearchParams {
+                page: page.or(params.page),
+                ..params.clone()
+            };
+            async move {
+                let page = params.page.unwrap_or_default();
+                let result = client.search_shared_voices(&params).await?;
+                let next = Some(page + 1).filter(|_| result.has_more);
+                Ok((result.voices, next))
+            }
+        })
+    }
+
+    /// Adds a voice of the public voice library to the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_user_id` - The ID of the user who shared the voice, from `SharedVoice::public_owner_id`.
+    /// * `voice_id` - The ID of the shared voice.
+    /// * `new_name` - The name of the voice in the account.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that, on success, contains the ID of the voice in the account,
+    /// or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::VoicesClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// let voice_id = voices_client.add_shared_voice("public_user_id", "voice_id", "Narrator - Audiobook").await?;
+    /// println!("Added voice ID: {}", voice_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_shared_voice(&self, public_user_id: &str, voice_id: &str, new_name: &str) -> Result<String, Error> {
+        let url = format!("{}/v1/voices/add/{}/{}", &self.config.api_url, public_user_id, voice_id);
+        let request = AddSharedVoiceRequest { new_name };
+
+        let response = send_with_retry(&self.config.retry_policy, false, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key)
+                .json(&request))
+        })
+        .await?;
+
+        Ok(response.json::<AddVoiceResponse>().await?.voice_id)
     }
 
     /// Deletes a voice by its ID.
@@ -95,6 +724,7 @@ impl VoicesClient {
     /// # Examples
     ///
     /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::VoicesClient};
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
     /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
     /// let voices_client = VoicesClient::new(config);
@@ -102,45 +732,49 @@ impl VoicesClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete_voice(&self, voice_id: &str) -> Result<(), UtilsError> {
+    pub async fn delete_voice(&self, voice_id: &str) -> Result<(), Error> {
         let url = format!("{}/v1/voices/{}", &self.config.api_url, voice_id);
 
-        let response = create_request(&self.client, reqwest::Method::DELETE, &url)
-            .header("xi-api-key", &self.config.api_key)
-            .send()
-            .await
-            .map_err(UtilsError::Http)?;
+        send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::DELETE, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            let error_msg = format!("🚨 Failed to delete voice: HTTP {}", response.status());
-            Err(UtilsError::Custom(error_msg))
-        }
+        Ok(())
     }
     
-    /// Adds a new voice to the collection of voices in VoiceLab.
+    /// Adds a new voice to the collection of voices in VoiceLab, cloned from audio samples.
     ///
     /// # Arguments
     ///
     /// * `name` - The name that identifies the voice.
-    /// * `files` - One or more audio files to clone the voice from.
-    /// * `description` - A description for the voice.
-    /// * `labels` - Serialized labels dictionary for the voice.
+    /// * `files` - One or more audio samples to clone the voice from: file paths, bytes in
+    ///   memory or async readers.
+    /// * `options` - The description, labels and noise removal of the voice.
     ///
     /// # Returns
     ///
-    /// A `Result` that, on success, contains the voice ID of the added voice(s),
-    /// or `UtilsError` on failure.
+    /// A `Result` that, on success, contains the voice ID of the added voice,
+    /// or `Error` on failure.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use elevenlabs_rust::{Config, utils::FileUpload, voices::{VoiceCloneOptions, VoicesClient}};
+    /// # async fn run(sample: bytes::Bytes) -> Result<(), Box<dyn std::error::Error>> {
     /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
     /// let voices_client = VoicesClient::new(config);
-    /// let files = vec!["path_to_audio_file_1", "path_to_audio_file_2"];
-    /// let voice_id = voices_client.add_voice("New Voice Name", files, Some("Description"), None).await?;
+    /// let files = vec![
+    ///     FileUpload::from_path("path_to_audio_file_1.mp3"),
+    ///     FileUpload::from_bytes(sample, "sample_2.wav"),
+    /// ];
+    /// let options = VoiceCloneOptions {
+    ///     description: Some("Description".to_string()),
+    ///     remove_background_noise: Some(true),
+    ///     ..Default::default()
+    /// };
+    /// let voice_id = voices_client.add_voice("New Voice Name", files, &options).await?;
     /// println!("Added voice ID: {}", voice_id);
     /// # Ok(())
     /// # }
@@ -148,41 +782,14 @@ impl VoicesClient {
     pub async fn add_voice(
         &self,
         name: &str,
-        files: Vec<&str>, // Assuming paths to files
-        description: Option<&str>,
-        labels: Option<&str>,
-    ) -> Result<String, UtilsError> {
+        files: Vec<FileUpload>,
+        options: &VoiceCloneOptions,
+    ) -> Result<String, Error> {
         let url = format!("{}/v1/voices/add", &self.config.api_url);
 
-        let mut form = multipart::Form::new().text("name", name.to_string());
-
-        for file_path in files {
-            let file = multipart::Part::file(file_path)?;
-            form = form.part("files", file);
-        }
-
-        if let Some(description) = description {
-            form = form.text("description", description.to_string());
-        }
-
-        if let Some(labels) = labels {
-            form = form.text("labels", labels.to_string());
-        }
-
-        let response = self.client.post(url)
-            .header("xi-api-key", &self.config.api_key)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(UtilsError::Http)?;
+        let response = self.send_voice_form(&url, name, files, options).await?;
 
-        if response.status().is_success() {
-            let voice_id = response.text().await.map_err(UtilsError::Http)?;
-            Ok(voice_id)
-        } else {
-            let error_msg = format!("Failed to add voice: HTTP {}", response.status());
-            Err(UtilsError::Custom(error_msg))
-        }
+        Ok(response.json::<AddVoiceResponse>().await?.voice_id)
     }
 
     /// Edits an existing voice.
@@ -191,9 +798,8 @@ impl VoicesClient {
     ///
     /// * `voice_id` - The ID of the voice to edit.
     /// * `name` - The new name of the voice.
-    /// * `files` - Audio files to add to the voice.
-    /// * `description` - New description for the voice.
-    /// * `labels` - Serialized labels dictionary for the voice.
+    /// * `files` - Audio samples to add to the voice: file paths, bytes in memory or async readers.
+    /// * `options` - The new description and labels, and the noise removal of the new samples.
     ///
     /// # Returns
     ///
@@ -202,10 +808,15 @@ impl VoicesClient {
     /// # Examples
     ///
     /// ```no_run
+    /// # use elevenlabs_rust::{Config, utils::FileUpload, voices::{VoiceCloneOptions, VoicesClient}};
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
     /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
     /// let voices_client = VoicesClient::new(config);
-    /// voices_client.edit_voice("voice_id_example", "New Voice Name", vec!["path_to_audio_file"], Some("New Description"), None).await?;
+    /// let options = VoiceCloneOptions {
+    ///     description: Some("New Description".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// voices_client.edit_voice("voice_id_example", "New Voice Name", vec![FileUpload::from_path("path_to_audio_file")], &options).await?;
     /// # Ok(())
     /// # }
     /// ```
@@ -213,43 +824,108 @@ impl VoicesClient {
         &self,
         voice_id: &str,
         name: &str,
-        files: Vec<&str>, // Assuming paths to files
-        description: Option<&str>,
-        labels: Option<&str>,
-    ) -> Result<(), UtilsError> {
+        files: Vec<FileUpload>,
+        options: &VoiceCloneOptions,
+    ) -> Result<(), Error> {
         let url = format!("{}/v1/voices/{}/edit", &self.config.api_url, voice_id);
 
-        let mut form = multipart::Form::new().text("name", name.to_string());
+        self.send_voice_form(&url, name, files, options).await?;
 
-        for file_path in files {
-            let file = multipart::Part::file(file_path)?;
-            form = form.part("files", file);
-        }
+        Ok(())
+    }
 
-        if let Some(description) = description {
-            form = form.text("description", description.to_string());
+    /// Uploads the multipart form shared by the add and edit voice endpoints.
+    ///
+    /// Both endpoints attach the uploaded samples to a voice, so a retry after the API may
+    /// already have acted would attach them twice.
+    async fn send_voice_form(
+        &self,
+        url: &str,
+        name: &str,
+        files: Vec<FileUpload>,
+        options: &VoiceCloneOptions,
+    ) -> Result<reqwest::Response, Error> {
+        let mut prepared = Vec::with_capacity(files.len());
+        for file in files {
+            prepared.push(file.prepare().await?);
         }
+        let labels = options.labels.as_ref().map(serde_json::to_string).transpose()?;
+        let retry_policy = if prepared.iter().all(PreparedUpload::is_replayable) {
+            self.config.retry_policy.clone()
+        } else {
+            RetryPolicy::none()
+        };
 
-        if let Some(labels) = labels {
-            form = form.text("labels", labels.to_string());
-        }
+        // Multipart bodies can't be cloned, so the form is rebuilt for every attempt.
+        send_with_retry(&retry_policy, false, || {
+            let mut form = multipart::Form::new().text("name", name.to_string());
+            for file in &prepared {
+                form = form.part("files", file.to_part()?);
+            }
+            if let Some(description) = &options.description {
+                form = form.text("description", description.clone());
+            }
+            if let Some(labels) = &labels {
+                form = form.text("labels", labels.clone());
+            }
+            if let Some(remove_background_noise) = options.remove_background_noise {
+                form = form.text("remove_background_noise", remove_background_noise.to_string());
+            }
+            Ok(self.client.post(url)
+                .header("xi-api-key", &self.config.api_key)
+                .multipart(form))
+        })
+        .await
+    }
 
-        let response = self.client.post(url)
-            .header("xi-api-key", &self.config.api_key)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(UtilsError::Http)?;
+    /// Fetches the settings new voices are created with.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the default `VoiceSettings`, or `Error` on failure.
+    pub async fn get_default_voice_settings(&self) -> Result<VoiceSettings, Error> {
+        let url = format!("{}/v1/voices/settings/default", &self.config.api_url);
+        self.get_settings(&url).await
+    }
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            let error_msg = format!("Failed to edit voice: HTTP {}", response.status());
-            Err(UtilsError::Custom(error_msg))
-        }
+    /// Fetches the settings stored for a specific voice, as last saved in the API or the web UI.
+    ///
+    /// # Arguments
+    ///
+    /// * `voice_id` - The ID of the voice whose settings are fetched.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `VoiceSettings` of the voice, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::VoicesClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// let settings = voices_client.get_voice_settings("voice_id_example").await?;
+    /// println!("Stability: {}", settings.stability);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_voice_settings(&self, voice_id: &str) -> Result<VoiceSettings, Error> {
+        let url = format!("{}/v1/voices/{}/settings", &self.config.api_url, voice_id);
+        self.get_settings(&url).await
+    }
+
+    async fn get_settings(&self, url: &str) -> Result<VoiceSettings, Error> {
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(response.json::<VoiceSettings>().await?)
     }
 
-        /// Edits the settings for a specific voice.
+    /// Edits the settings for a specific voice.
     ///
     /// # Arguments
     ///
@@ -263,15 +939,11 @@ impl VoicesClient {
     /// # Examples
     ///
     /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::{VoiceSettings, VoicesClient}};
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
     /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
     /// let voices_client = VoicesClient::new(config);
-    /// let settings = VoiceSettings {
-    ///     stability: 0.5,
-    ///     similarity_boost: 0.5,
-    ///     style: None,
-    ///     use_speaker_boost: Some(true),
-    /// };
+    /// let settings = VoiceSettings::new(0.5, 0.5)?.with_speaker_boost(true);
     /// voices_client.edit_voice_settings("voice_id_example", settings).await?;
     /// # Ok(())
     /// # }
@@ -280,22 +952,353 @@ impl VoicesClient {
         &self,
         voice_id: &str,
         settings: VoiceSettings,
-    ) -> Result<(), UtilsError> {
+    ) -> Result<(), Error> {
+        settings.validate()?;
         let url = format!("{}/v1/voices/{}/settings/edit", &self.config.api_url, voice_id);
 
-        let response = self.client.post(url)
-            .header("Content-Type", "application/json")
-            .header("xi-api-key", &self.config.api_key)
-            .json(&settings)
-            .send()
-            .await
-            .map_err(UtilsError::Http)?;
+        send_with_retry(&self.config.retry_policy, true, || {
+            Ok(self.client.post(&url)
+                .header("Content-Type", "application/json")
+                .header("xi-api-key", &self.config.api_key)
+                .json(&settings))
+        })
+        .await?;
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            let error_msg = format!("Failed to edit voice settings: HTTP {}", response.status());
-            Err(UtilsError::Custom(error_msg))
+        Ok(())
+    }
+
+    /// Downloads the audio of a sample of a voice.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the audio in the format of `Sample::mime_type`,
+    /// or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::VoicesClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// let voice = voices_client.get_voice_metadata("voice_id", false).await?;
+    /// for sample in &voice.samples {
+    ///     let audio = voices_client.get_sample_audio(&voice.voice_id, &sample.sample_id).await?;
+    ///     std::fs::write(sample.file_name.as_deref().unwrap_or(&sample.sample_id), &audio)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_sample_audio(&self, voice_id: &str, sample_id: &str) -> Result<Bytes, Error> {
+        let url = format!("{}/v1/voices/{}/samples/{}/audio", &self.config.api_url, voice_id, sample_id);
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(self.client.get(&url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(response.bytes().await?)
+    }
+
+    /// Deletes a sample of a voice.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or error.
+    pub async fn delete_sample(&self, voice_id: &str, sample_id: &str) -> Result<(), Error> {
+        let url = format!("{}/v1/voices/{}/samples/{}", &self.config.api_url, voice_id, sample_id);
+
+        send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::DELETE, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Starts separating the speakers of a sample, e.g. of a recorded interview. Poll
+    /// [`get_speaker_separation`](Self::get_speaker_separation) for the result.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or error.
+    pub async fn start_speaker_separation(&self, voice_id: &str, sample_id: &str) -> Result<(), Error> {
+        let url = format!(
+            "{}/v1/voices/pvc/{}/samples/{}/separate-speakers",
+            &self.config.api_url, voice_id, sample_id
+        );
+
+        send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::POST, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches the status of the speaker separation of a sample and the speakers it detected.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `SpeakerSeparation`, or `Error` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, voices::{SpeakerSeparationStatus, VoicesClient}};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::new("your_api_key", "https://api.elevenlabs.io");
+    /// let voices_client = VoicesClient::new(config);
+    /// let separation = voices_client.get_speaker_separation("voice_id", "sample_id").await?;
+    /// if separation.status == SpeakerSeparationStatus::Completed {
+    ///     for speaker in separation.speakers.values() {
+    ///         let audio = voices_client.get_speaker_audio("voice_id", "sample_id", &speaker.speaker_id).await?;
+    ///         std::fs::write(format!("{}.mp3", speaker.speaker_id), &audio.audio)?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_speaker_separation(&self, voice_id: &str, sample_id: &str) -> Result<SpeakerSeparation, Error> {
+        let url = format!(
+            "{}/v1/voices/pvc/{}/samples/{}/speakers",
+            &self.config.api_url, voice_id, sample_id
+        );
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        Ok(response.json::<SpeakerSeparation>().await?)
+    }
+
+    /// Downloads the isolated audio of one speaker of a sample, once the speaker separation
+    /// is completed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `SpeakerAudio`, or `Error` on failure.
+    pub async fn get_speaker_audio(&self, voice_id: &str, sample_id: &str, speaker_id: &str) -> Result<SpeakerAudio, Error> {
+        let url = format!(
+            "{}/v1/voices/pvc/{}/samples/{}/speakers/{}/audio",
+            &self.config.api_url, voice_id, sample_id, speaker_id
+        );
+
+        let response = send_with_retry(&self.config.retry_policy, true, || {
+            Ok(create_request(&self.client, reqwest::Method::GET, &url)
+                .header("xi-api-key", &self.config.api_key))
+        })
+        .await?;
+
+        let response = response.json::<SpeakerAudioResponse>().await?;
+        let audio = BASE64
+            .decode(response.audio_base_64)
+            .map_err(|err| Error::Protocol(format!("invalid speaker audio: {}", err)))?;
+
+        Ok(SpeakerAudio {
+            audio: Bytes::from(audio),
+            media_type: response.media_type,
+            duration_secs: response.duration_secs,
+        })
+    }
+}
+
+/// A query over the voice listing, started with [`VoicesClient::find_voices`].
+///
+/// The category is a strict filter. Each label criterion is compared, ignoring case, spaces,
+/// dashes, underscores and parentheses, with the voice label of the same name: an exact
+/// match ranks a voice higher than a partial one such as `american` for
+/// `american (southern)`, a voice without the label is kept but ranked lower, and a voice
+/// whose label differs is left out.
+pub struct VoiceQuery<'a> {
+    client: &'a VoicesClient,
+    category: Option<VoiceCategory>,
+    labels: Vec<(String, String)>,
+    limit: Option<usize>,
+}
+
+impl VoiceQuery<'_> {
+    /// Matches the `gender` label, e.g. `female`.
+    pub fn gender(self, gender: &str) -> Self {
+        self.label("gender", gender)
+    }
+
+    /// Matches the `accent` label, e.g. `british`.
+    pub fn accent(self, accent: &str) -> Self {
+        self.label("accent", accent)
+    }
+
+    /// Matches the `age` label, e.g. `young`.
+    pub fn age(self, age: &str) -> Self {
+        self.label("age", age)
+    }
+
+    /// Matches the `use_case` label, e.g. `narration`.
+    pub fn use_case(self, use_case: &str) -> Self {
+        self.label("use_case", use_case)
+    }
+
+    /// Matches any other label, e.g. `description`.
+    pub fn label(mut self, key: &str, value: &str) -> Self {
+        self.labels.push((key.to_string(), normalize_label(value)));
+        self
+    }
+
+    /// Keeps only voices of `category`.
+    pub fn category(mut self, category: VoiceCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Returns at most `limit` voices.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Fetches the voice listing, or reuses the cached one, and ranks it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the matching `Voice`s, best match first, or
+    /// `Error` on failure.
+    pub async fn fetch(&self) -> Result<Vec<Voice>, Error> {
+        let voices = self.client.voice_listing().await?;
+        Ok(self.rank(&voices))
+    }
+
+    /// Ranks the given voices, best match first, leaving out the ones that don't match.
+    /// Voices that match equally well are ordered by name.
+    pub fn rank(&self, voices: &[Voice]) -> Vec<Voice> {
+        let mut ranked: Vec<(u32, &Voice)> = voices
+            .iter()
+            .filter_map(|voice| self.score(voice).map(|score| (score, voice)))
+            .collect();
+        ranked.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then_with(|| a.name.cmp(&b.name)));
+
+        ranked
+            .into_iter()
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(|(_, voice)| voice.clone())
+            .collect()
+    }
+
+    /// Returns how well `voice` matches, or `None` if it doesn't.
+    fn score(&self, voice: &Voice) -> Option<u32> {
+        if self.category.is_some() && voice.category != self.category {
+            return None;
+        }
+
+        let mut score = 0;
+        for (key, wanted) in &self.labels {
+            let Some(value) = voice.labels.get(key) else {
+                continue;
+            };
+            let value = normalize_label(value);
+            if value == *wanted {
+                score += 2;
+            } else if contains_words(&value, wanted) || contains_words(wanted, &value) {
+                score += 1;
+            } else {
+                return None;
+            }
         }
+        Some(score)
+    }
+}
+
+/// Returns whether every word of `part` appears in `label`.
+fn contains_words(label: &str, part: &str) -> bool {
+    part.split(' ').all(|word| label.split(' ').any(|candidate| candidate == word))
+}
+
+/// Lowercases a label and treats dashes, underscores and parentheses as spaces.
+fn normalize_label(value: &str) -> String {
+    value
+        .to_lowercase()
+        .replace(['-', '_', '(', ')'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voice(name: &str, category: &str, labels: &[(&str, &str)]) -> Voice {
+        let labels: HashMap<&str, &str> = labels.iter().copied().collect();
+        serde_json::from_value(serde_json::json!({
+            "voice_id": name.to_lowercase(),
+            "name": name,
+            "category": category,
+            "labels": labels,
+        }))
+        .unwrap()
+    }
+
+    fn names(voices: &[Voice]) -> Vec<&str> {
+        voices.iter().map(|voice| voice.name.as_str()).collect()
+    }
+
+    #[test]
+    fn labels_are_normalized() {
+        assert_eq!(normalize_label("  American (Southern) "), "american southern");
+        assert_eq!(normalize_label("middle-aged"), "middle aged");
+        assert_eq!(normalize_label("Social_Media"), "social media");
+        assert!(contains_words("american southern", "american"));
+        assert!(!contains_words("american southern", "south"));
+        assert!(!contains_words("american", "american southern"));
+    }
+
+    #[test]
+    fn exact_matches_rank_above_partial_ones() {
+        let client = VoicesClient::new(Config::new("key", "http://localhost"));
+        let voices = [
+            voice("Southern", "premade", &[("accent", "american (southern)")]),
+            voice("Plain", "premade", &[("accent", "American")]),
+        ];
+
+        let ranked = client.find_voices().accent("american").rank(&voices);
+        assert_eq!(names(&ranked), ["Plain", "Southern"]);
+        let ranked = client.find_voices().accent("American-Southern").rank(&voices);
+        assert_eq!(names(&ranked), ["Southern", "Plain"]);
+    }
+
+    #[test]
+    fn voices_without_the_label_rank_last_and_others_are_dropped() {
+        let client = VoicesClient::new(Config::new("key", "http://localhost"));
+        let voices = [
+            voice("Unlabelled", "premade", &[]),
+            voice("British", "premade", &[("accent", "british"), ("gender", "female")]),
+            voice("American", "premade", &[("accent", "american"), ("gender", "female")]),
+            voice("Male", "premade", &[("accent", "american"), ("gender", "male")]),
+        ];
+
+        let ranked = client.find_voices().accent("american").gender("female").rank(&voices);
+        assert_eq!(names(&ranked), ["American", "Unlabelled"]);
+        let ranked = client.find_voices().gender("female").limit(2).rank(&voices);
+        assert_eq!(names(&ranked), ["American", "British"]);
+    }
+
+    #[test]
+    fn category_filters_strictly() {
+        let client = VoicesClient::new(Config::new("key", "http://localhost"));
+        let mut voices = vec![
+            voice("Cloned", "cloned", &[("accent", "american")]),
+            voice("Premade", "premade", &[]),
+            voice("Uncategorized", "premade", &[]),
+        ];
+        voices[2].category = None;
+
+        let ranked = client.find_voices().category(VoiceCategory::Premade).accent("american").rank(&voices);
+        assert_eq!(names(&ranked), ["Premade"]);
+        let ranked = client.find_voices().category(VoiceCategory::Professional).rank(&voices);
+        assert!(ranked.is_empty());
     }
 }
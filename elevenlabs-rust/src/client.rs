@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use reqwest::Client;
+
+use crate::api::history::HistoryClient;
+use crate::api::models::ModelsClient;
+use crate::api::pronunciation_dictionaries::PronunciationDictionaryClient;
+use crate::api::pvc::PvcClient;
+use crate::api::sts::SpeechToSpeechClient;
+use crate::api::tts::TextToSpeechClient;
+use crate::api::user::UserClient;
+use crate::api::voice_generation::VoiceGenerationClient;
+use crate::api::voices::VoicesClient;
+use crate::config::Config;
+
+/// Entry point to every ElevenLabs API.
+///
+/// `ElevenLabsClient` owns a single HTTP connection pool and a single `Config`. The
+/// per-API handles it returns are cheap to clone and all share that pool, so it should
+/// be built once and reused for the lifetime of the application.
+#[derive(Clone)]
+pub struct ElevenLabsClient {
+    client: Client,
+    config: Arc<Config>,
+    models: ModelsClient,
+    tts: TextToSpeechClient,
+    voices: VoicesClient,
+}
+
+impl ElevenLabsClient {
+    /// Creates a new `ElevenLabsClient` using the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A `Config` instance containing the necessary configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use elevenlabs_rust::{Config, ElevenLabsClient};
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ElevenLabsClient::new(Config::new("your_api_key", "https://api.elevenlabs.io"));
+    /// let user_info = client.user().get_user_info().await?;
+    /// println!("Tier: {}", user_info.subscription.tier);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(config: Config) -> Self {
+        Self::with_http_client(Client::new(), config)
+    }
+
+    /// Creates a new `ElevenLabsClient` around a preconfigured `reqwest::Client`, e.g. one
+    /// with custom timeouts or a proxy.
+    pub fn with_http_client(client: Client, config: Config) -> Self {
+        let config = Arc::new(config);
+        let models = ModelsClient::from_parts(client.clone(), config.clone());
+        ElevenLabsClient {
+            tts: TextToSpeechClient::from_parts(client.clone(), config.clone()).with_models(models.clone()),
+            models,
+            voices: VoicesClient::from_parts(client.clone(), config.clone()),
+            client,
+            config,
+        }
+    }
+
+    /// Returns the configuration shared by every handle.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns a Text-to-Speech handle sharing this client's connection pool. Every handle
+    /// returned by this client shares one cache of stored voice settings.
+    pub fn tts(&self) -> TextToSpeechClient {
+        self.tts.clone()
+    }
+
+    /// Returns a Voices handle sharing this client's connection pool. Every handle returned
+    /// by this client shares one cached voice listing.
+    pub fn voices(&self) -> VoicesClient {
+        self.voices.clone()
+    }
+
+    /// Returns a User handle sharing this client's connection pool.
+    pub fn user(&self) -> UserClient {
+        UserClient::from_parts(self.client.clone(), self.config.clone())
+    }
+
+    /// Returns a Speech-to-Speech handle sharing this client's connection pool.
+    pub fn sts(&self) -> SpeechToSpeechClient {
+        SpeechToSpeechClient::from_parts(self.client.clone(), self.config.clone()).with_models(self.models.clone())
+    }
+
+    /// Returns a Voice Generation handle sharing this client's connection pool.
+    pub fn voice_generation(&self) -> VoiceGenerationClient {
+        VoiceGenerationClient::from_parts(self.client.clone(), self.config.clone())
+    }
+
+    /// Returns a History handle sharing this client's connection pool.
+    pub fn history(&self) -> HistoryClient {
+        HistoryClient::from_parts(self.client.clone(), self.config.clone())
+    }
+
+    /// Returns a Pronunciation Dictionaries handle sharing this client's connection pool.
+    pub fn pronunciation_dictionaries(&self) -> PronunciationDictionaryClient {
+        PronunciationDictionaryClient::from_parts(self.client.clone(), self.config.clone())
+    }
+
+    /// Returns a Professional Voice Cloning handle sharing this client's connection pool.
+    pub fn pvc(&self) -> PvcClient {
+        PvcClient::from_parts(self.client.clone(), self.config.clone())
+    }
+
+    /// Returns a Models handle. Every handle returned by this client, including the
+    /// Text-to-Speech and Speech-to-Speech ones, shares one model cache.
+    pub fn models(&self) -> ModelsClient {
+        self.models.clone()
+    }
+}
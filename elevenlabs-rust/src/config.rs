@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use reqwest::header::HeaderMap;
+
+use crate::api::utils::budget::parse_character_cost;
+use crate::api::utils::{load_api_key, load_api_url, BudgetCharge, BudgetLabels, CharacterBudget, RetryPolicy};
+use crate::error::Error;
+
+/// The default base URL of the ElevenLabs API.
+pub const DEFAULT_API_URL: &str = "https://api.elevenlabs.io";
+
+/// Configuration shared by every ElevenLabs API client.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub api_key: String,
+    pub api_url: String,
+    pub retry_policy: RetryPolicy,
+    /// Caps the characters spent by the clients using this configuration.
+    pub budget: Option<Arc<CharacterBudget>>,
+    /// What the calls made with this configuration are counted as by the budget.
+    pub budget_labels: BudgetLabels,
+}
+
+impl Config {
+    /// Creates a new `Config` from an API key and the base URL of the API.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - Your ElevenLabs API key.
+    /// * `api_url` - The base URL of the API, e.g. `https://api.elevenlabs.io`.
+    pub fn new(api_key: &str, api_url: &str) -> Self {
+        Config {
+            api_key: api_key.to_string(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            retry_policy: RetryPolicy::default(),
+            budget: None,
+            budget_labels: BudgetLabels::default(),
+        }
+    }
+
+    /// Replaces the policy used to retry rate limited and failed requests.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Attaches a character budget. The same budget can be shared by several configurations,
+    /// e.g. one per job.
+    pub fn with_budget(mut self, budget: Arc<CharacterBudget>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Counts the calls made with this configuration against the limit of `job`.
+    pub fn with_budget_job(mut self, job: &str) -> Self {
+        self.budget_labels.job = Some(job.to_string());
+        self
+    }
+
+    /// Counts the calls made with this configuration against the limit of `tag`.
+    pub fn with_budget_tag(mut self, tag: &str) -> Self {
+        self.budget_labels.tags.push(tag.to_string());
+        self
+    }
+
+    /// Charges `text` to the budget, if any, before a request is sent.
+    pub(crate) fn charge_budget(&self, text: &str) -> Result<Option<BudgetCharge>, Error> {
+        self.budget
+            .as_ref()
+            .map(|budget| budget.charge(&self.budget_labels, text.chars().count() as u64))
+            .transpose()
+    }
+
+    /// Returns the characters of `charge` after a request failed.
+    pub(crate) fn refund_budget(&self, charge: Option<BudgetCharge>) {
+        if let (Some(budget), Some(charge)) = (&self.budget, charge) {
+            budget.refund(&self.budget_labels, charge);
+        }
+    }
+
+    /// Refuses a request whose cost is not known in advance once the budget is exhausted.
+    pub(crate) fn check_budget(&self) -> Result<(), Error> {
+        match &self.budget {
+            Some(budget) => budget.check(&self.budget_labels),
+            None => Ok(()),
+        }
+    }
+
+    /// Records the `character-cost` reported with a response.
+    pub(crate) fn record_budget(&self, headers: &HeaderMap) {
+        if let (Some(budget), Some(cost)) = (&self.budget, parse_character_cost(headers)) {
+            budget.record(&self.budget_labels, cost);
+        }
+    }
+
+    /// Creates a new `Config` from the `ELEVENLABS_API_KEY` and `ELEVENLABS_API_URL`
+    /// environment variables. The URL falls back to [`DEFAULT_API_URL`] when unset.
+    pub fn from_env() -> Result<Self, std::env::VarError> {
+        let api_key = load_api_key()?;
+        let api_url = load_api_url().unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+        Ok(Config::new(&api_key, &api_url))
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Response, StatusCode};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::api::utils::BudgetExceeded;
+
+/// Errors returned by every ElevenLabs API client.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The API key is missing or invalid (HTTP 401).
+    #[error("unauthorized: {0}")]
+    Unauthorized(ApiError),
+    /// The account has run out of characters or credits.
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(ApiError),
+    /// Too many requests (HTTP 429). `retry_after` holds the `Retry-After` delay, if any.
+    #[error("rate limited: {error}")]
+    RateLimited {
+        error: ApiError,
+        retry_after: Option<Duration>,
+    },
+    /// The request was rejected by the API's validation (HTTP 422).
+    #[error("validation failed: {error}")]
+    Validation {
+        error: ApiError,
+        details: Vec<ValidationDetail>,
+    },
+    /// The requested resource does not exist (HTTP 404).
+    #[error("not found: {0}")]
+    NotFound(ApiError),
+    /// The API failed to process the request (HTTP 5xx).
+    #[error("server error: {0}")]
+    Server(ApiError),
+    /// Any other unsuccessful response.
+    #[error("API error: {0}")]
+    Api(ApiError),
+    /// The request could not be sent or the response could not be read.
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    /// A local file could not be read or written.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    /// A response body could not be decoded.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The request was rejected before it was sent.
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    /// The call was refused because it would exceed a `CharacterBudget`.
+    #[error("character budget exceeded: {0}")]
+    BudgetExceeded(BudgetExceeded),
+    /// A PLS lexicon could not be parsed.
+    #[error("lexicon error: {0}")]
+    Lexicon(String),
+    /// The WebSocket connection failed.
+    #[error("WebSocket error: {0}")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+    /// The server reported an error or sent a message that could not be interpreted.
+    #[error("protocol error: {0}")]
+    Protocol(String),
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocket(Box::new(err))
+    }
+}
+
+impl Error {
+    /// Builds an `Error` from an unsuccessful response, consuming its body.
+    pub(crate) async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let retry_after = parse_retry_after(response.headers());
+        let request_id = parse_request_id(response.headers());
+        let raw_body = response.text().await.unwrap_or_default();
+        let body = serde_json::from_str::<ErrorBody>(&raw_body).ok().map(Box::new);
+
+        Error::from_api_error(
+            ApiError {
+                status,
+                request_id,
+                body,
+                raw_body,
+            },
+            retry_after,
+        )
+    }
+
+    fn from_api_error(error: ApiError, retry_after: Option<Duration>) -> Self {
+        let detail_status = error.detail_status().unwrap_or_default();
+
+        if detail_status == "quota_exceeded" || error.status == StatusCode::PAYMENT_REQUIRED {
+            return Error::QuotaExceeded(error);
+        }
+
+        match error.status {
+            StatusCode::UNAUTHORIZED => Error::Unauthorized(error),
+            StatusCode::NOT_FOUND => Error::NotFound(error),
+            StatusCode::TOO_MANY_REQUESTS => Error::RateLimited { error, retry_after },
+            StatusCode::UNPROCESSABLE_ENTITY => {
+                let details = match error.body.as_deref() {
+                    Some(ErrorBody {
+                        detail: ErrorDetail::Validation(details),
+                    }) => details.clone(),
+                    _ => Vec::new(),
+                };
+                Error::Validation { error, details }
+            }
+            status if status.is_server_error() => Error::Server(error),
+            _ => Error::Api(error),
+        }
+    }
+
+    /// Returns the API error attached to this error, if the API answered at all.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Error::Unauthorized(error)
+            | Error::QuotaExceeded(error)
+            | Error::RateLimited { error, .. }
+            | Error::Validation { error, .. }
+            | Error::NotFound(error)
+            | Error::Server(error)
+            | Error::Api(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns the HTTP status of the response that caused this error.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Http(err) => err.status(),
+            _ => self.api_error().map(|error| error.status),
+        }
+    }
+
+    /// Returns the ID the API assigned to the failed request, if it reported one.
+    pub fn request_id(&self) -> Option<&str> {
+        self.api_error().and_then(|error| error.request_id.as_deref())
+    }
+}
+
+/// The details of an unsuccessful API response.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// The HTTP status of the response.
+    pub status: StatusCode,
+    /// The value of the `request-id` response header.
+    pub request_id: Option<String>,
+    /// The parsed response body, when it followed the API's error schema.
+    pub body: Option<Box<ErrorBody>>,
+    /// The raw response body.
+    pub raw_body: String,
+}
+
+impl ApiError {
+    /// Returns the machine readable `detail.status` of the error, e.g. `quota_exceeded`.
+    pub fn detail_status(&self) -> Option<&str> {
+        match self.body.as_deref() {
+            Some(ErrorBody {
+                detail: ErrorDetail::Status { status, .. },
+            }) => Some(status),
+            _ => None,
+        }
+    }
+
+    /// Returns the human readable message of the error.
+    pub fn message(&self) -> &str {
+        match self.body.as_deref() {
+            Some(ErrorBody {
+                detail: ErrorDetail::Status { message, .. },
+            })
+            | Some(ErrorBody {
+                detail: ErrorDetail::Message(message),
+            }) => message,
+            Some(ErrorBody {
+                detail: ErrorDetail::Validation(details),
+            }) if !details.is_empty() => &details[0].msg,
+            _ => &self.raw_body,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HTTP {}", self.status)?;
+        let message = self.message();
+        if !message.is_empty() {
+            write!(f, ": {}", message)?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request id {})", request_id)?;
+        }
+        Ok(())
+    }
+}
+
+/// The body of an error response.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ErrorBody {
+    pub detail: ErrorDetail,
+}
+
+/// The `detail` field of an error response, which the API shapes differently per error.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ErrorDetail {
+    /// `{"status": "...", "message": "..."}`
+    Status { status: String, message: String },
+    /// The list of field errors of a 422 response.
+    Validation(Vec<ValidationDetail>),
+    /// A plain message.
+    Message(String),
+}
+
+/// A single field error of a 422 response.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ValidationDetail {
+    /// The path to the offending field, e.g. `["body", "text"]`.
+    #[serde(default)]
+    pub loc: Vec<serde_json::Value>,
+    pub msg: String,
+    #[serde(rename = "type", default)]
+    pub kind: String,
+}
+
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+pub(crate) fn parse_request_id(headers: &HeaderMap) -> Option<String> {
+    ["request-id", "x-request-id"]
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn retry_after(value: &str) -> Option<Duration> {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        parse_retry_after(&headers)
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        assert_eq!(retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(retry_after(" 1.5 "), Some(Duration::from_millis(1500)));
+        assert_eq!(retry_after("0"), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn ignores_invalid_retry_after() {
+        assert_eq!(retry_after("-1"), None);
+        assert_eq!(retry_after("NaN"), None);
+        assert_eq!(retry_after("inf"), None);
+        assert_eq!(retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(parse_retry_after(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn rate_limited_responses_carry_retry_after() {
+        let error = Error::from_api_error(
+            ApiError {
+                status: StatusCode::TOO_MANY_REQUESTS,
+                request_id: None,
+                body: None,
+                raw_body: String::new(),
+            },
+            Some(Duration::from_secs(2)),
+        );
+
+        assert!(matches!(error, Error::RateLimited { retry_after: Some(delay), .. } if delay == Duration::from_secs(2)));
+    }
+}
@@ -1,21 +1,23 @@
 // Import necessary modules from your elevenlabs-rust crate
-use elevenlabs_rust::tts::{TextToSpeechClient, TtsRequest};
-use elevenlabs_rust::config::Config;
-use elevenlabs_rust::utils::UtilsError;
+use elevenlabs_rust::tts::TtsRequest;
+use elevenlabs_rust::{Config, ElevenLabsClient};
+use elevenlabs_rust::Error;
 
 // This is the entry point for the example.
 #[tokio::main] // This attribute is required for async main functions.
-async fn main() -> Result<(), UtilsError> {
+async fn main() -> Result<(), Error> {
     // Initialize configuration for the TTS client with your API key and the API endpoint.
     let config = Config::new("your_api_key_here", "https://api.elevenlabs.io");
 
-    // Create an instance of the TTS client using the configuration.
-    let tts_client = TextToSpeechClient::new(config);
+    // Build the top-level client once; every handle it returns shares one connection pool.
+    let client = ElevenLabsClient::new(config);
+    let tts_client = client.tts();
 
     // Create the TTS request with the text you want to convert to speech.
     let tts_request = TtsRequest {
         text: "Hello, world!".to_string(),
         // Include additional fields based on the ElevenLabs API documentation.
+        ..Default::default()
     };
 
     // Send the TTS request and await the response.
@@ -1,3 +1,15 @@
 
 
 pub mod api;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod models;
+pub mod pls;
+
+pub use api::{history, pronunciation_dictionaries, pvc, sts, tts, tts_websocket, user, utils, voice_generation, voices};
+pub use api::models::ModelsClient;
+pub use api::utils::{CharacterBudget, RetryPolicy};
+pub use client::ElevenLabsClient;
+pub use config::Config;
+pub use error::Error;
@@ -0,0 +1,608 @@
+use std::fmt;
+use std::str::FromStr;
+
+use bytes::{Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Settings that control how a voice sounds, shared by the Text-to-Speech, Speech-to-Speech
+/// and Voices APIs.
+///
+/// Build it with [`VoiceSettings::new`] and the `with_*` methods, which reject values outside
+/// the ranges the API accepts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VoiceSettings {
+    /// How consistent the delivery is, from `0.0` (more expressive) to `1.0` (more stable).
+    pub stability: f32,
+    /// How closely the output adheres to the original voice, from `0.0` to `1.0`.
+    pub similarity_boost: f32,
+    /// How much the style of the original speaker is exaggerated, from `0.0` to `1.0`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<f32>,
+    /// Whether to boost the similarity to the original speaker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_speaker_boost: Option<bool>,
+    /// The speaking rate, from `0.7` (slower) to `1.2` (faster). `1.0` is the natural rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+}
+
+impl VoiceSettings {
+    /// Creates new `VoiceSettings` with the given stability and similarity boost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use elevenlabs_rust::models::VoiceSettings;
+    /// # fn run() -> Result<(), elevenlabs_rust::Error> {
+    /// let settings = VoiceSettings::new(0.5, 0.75)?
+    ///     .with_style(0.2)?
+    ///     .with_speaker_boost(true)
+    ///     .with_speed(1.1)?;
+    /// assert!(VoiceSettings::new(1.5, 0.75).is_err());
+    /// # Ok(())
+    /// # }
+    /// # run().unwrap();
+    /// ```
+    pub fn new(stability: f32, similarity_boost: f32) -> Result<Self, Error> {
+        check_range("stability", stability, 0.0, 1.0)?;
+        check_range("similarity_boost", similarity_boost, 0.0, 1.0)?;
+
+        Ok(VoiceSettings {
+            stability,
+            similarity_boost,
+            style: None,
+            use_speaker_boost: None,
+            speed: None,
+        })
+    }
+
+    /// Sets the style exaggeration, from `0.0` to `1.0`.
+    pub fn with_style(mut self, style: f32) -> Result<Self, Error> {
+        check_range("style", style, 0.0, 1.0)?;
+        self.style = Some(style);
+        Ok(self)
+    }
+
+    /// Enables or disables the speaker boost.
+    pub fn with_speaker_boost(mut self, use_speaker_boost: bool) -> Self {
+        self.use_speaker_boost = Some(use_speaker_boost);
+        self
+    }
+
+    /// Sets the speaking rate, from `0.7` to `1.2`.
+    pub fn with_speed(mut self, speed: f32) -> Result<Self, Error> {
+        check_range("speed", speed, 0.7, 1.2)?;
+        self.speed = Some(speed);
+        Ok(self)
+    }
+
+    /// Checks that every value is within the range the API accepts. The clients call this
+    /// before sending settings, so values assigned directly to the fields are caught too.
+    pub fn validate(&self) -> Result<(), Error> {
+        check_range("stability", self.stability, 0.0, 1.0)?;
+        check_range("similarity_boost", self.similarity_boost, 0.0, 1.0)?;
+        if let Some(style) = self.style {
+            check_range("style", style, 0.0, 1.0)?;
+        }
+        if let Some(speed) = self.speed {
+            check_range("speed", speed, 0.7, 1.2)?;
+        }
+        Ok(())
+    }
+}
+
+fn check_range(name: &str, value: f32, min: f32, max: f32) -> Result<(), Error> {
+    if (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(Error::InvalidRequest(format!(
+            "{} must be between {} and {}, got {}",
+            name, min, max, value
+        )))
+    }
+}
+
+/// The audio encodings the API can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioEncoding {
+    Mp3,
+    /// Raw signed 16-bit little-endian PCM samples.
+    Pcm,
+    /// G.711 μ-law, as used by most telephony stacks.
+    Ulaw,
+    /// G.711 A-law.
+    Alaw,
+    /// Opus in an Ogg container.
+    Opus,
+}
+
+/// The output formats supported by the Text-to-Speech and Speech-to-Speech APIs, named
+/// after the API's `codec_samplerate[_bitrate]` identifiers.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OutputFormat {
+    #[serde(rename = "mp3_22050_32")]
+    Mp3_22050_32,
+    #[serde(rename = "mp3_44100_32")]
+    Mp3_44100_32,
+    #[serde(rename = "mp3_44100_64")]
+    Mp3_44100_64,
+    #[serde(rename = "mp3_44100_96")]
+    Mp3_44100_96,
+    #[default]
+    #[serde(rename = "mp3_44100_128")]
+    Mp3_44100_128,
+    #[serde(rename = "mp3_44100_192")]
+    Mp3_44100_192,
+    #[serde(rename = "pcm_8000")]
+    Pcm8000,
+    #[serde(rename = "pcm_16000")]
+    Pcm16000,
+    #[serde(rename = "pcm_22050")]
+    Pcm22050,
+    #[serde(rename = "pcm_24000")]
+    Pcm24000,
+    #[serde(rename = "pcm_44100")]
+    Pcm44100,
+    #[serde(rename = "pcm_48000")]
+    Pcm48000,
+    #[serde(rename = "ulaw_8000")]
+    Ulaw8000,
+    #[serde(rename = "alaw_8000")]
+    Alaw8000,
+    #[serde(rename = "opus_48000_32")]
+    Opus48000_32,
+    #[serde(rename = "opus_48000_64")]
+    Opus48000_64,
+    #[serde(rename = "opus_48000_96")]
+    Opus48000_96,
+    #[serde(rename = "opus_48000_128")]
+    Opus48000_128,
+    #[serde(rename = "opus_48000_192")]
+    Opus48000_192,
+}
+
+impl OutputFormat {
+    /// Every supported output format.
+    pub const ALL: [OutputFormat; 19] = [
+        OutputFormat::Mp3_22050_32,
+        OutputFormat::Mp3_44100_32,
+        OutputFormat::Mp3_44100_64,
+        OutputFormat::Mp3_44100_96,
+        OutputFormat::Mp3_44100_128,
+        OutputFormat::Mp3_44100_192,
+        OutputFormat::Pcm8000,
+        OutputFormat::Pcm16000,
+        OutputFormat::Pcm22050,
+        OutputFormat::Pcm24000,
+        OutputFormat::Pcm44100,
+        OutputFormat::Pcm48000,
+        OutputFormat::Ulaw8000,
+        OutputFormat::Alaw8000,
+        OutputFormat::Opus48000_32,
+        OutputFormat::Opus48000_64,
+        OutputFormat::Opus48000_96,
+        OutputFormat::Opus48000_128,
+        OutputFormat::Opus48000_192,
+    ];
+
+    /// Returns the identifier the API uses for this format, e.g. `mp3_44100_128`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Mp3_22050_32 => "mp3_22050_32",
+            OutputFormat::Mp3_44100_32 => "mp3_44100_32",
+            OutputFormat::Mp3_44100_64 => "mp3_44100_64",
+            OutputFormat::Mp3_44100_96 => "mp3_44100_96",
+            OutputFormat::Mp3_44100_128 => "mp3_44100_128",
+            OutputFormat::Mp3_44100_192 => "mp3_44100_192",
+            OutputFormat::Pcm8000 => "pcm_8000",
+            OutputFormat::Pcm16000 => "pcm_16000",
+            OutputFormat::Pcm22050 => "pcm_22050",
+            OutputFormat::Pcm24000 => "pcm_24000",
+            OutputFormat::Pcm44100 => "pcm_44100",
+            OutputFormat::Pcm48000 => "pcm_48000",
+            OutputFormat::Ulaw8000 => "ulaw_8000",
+            OutputFormat::Alaw8000 => "alaw_8000",
+            OutputFormat::Opus48000_32 => "opus_48000_32",
+            OutputFormat::Opus48000_64 => "opus_48000_64",
+            OutputFormat::Opus48000_96 => "opus_48000_96",
+            OutputFormat::Opus48000_128 => "opus_48000_128",
+            OutputFormat::Opus48000_192 => "opus_48000_192",
+        }
+    }
+
+    /// Returns the encoding of the audio.
+    pub fn encoding(&self) -> AudioEncoding {
+        match self {
+            OutputFormat::Mp3_22050_32
+            | OutputFormat::Mp3_44100_32
+            | OutputFormat::Mp3_44100_64
+            | OutputFormat::Mp3_44100_96
+            | OutputFormat::Mp3_44100_128
+            | OutputFormat::Mp3_44100_192 => AudioEncoding::Mp3,
+            OutputFormat::Pcm8000
+            | OutputFormat::Pcm16000
+            | OutputFormat::Pcm22050
+            | OutputFormat::Pcm24000
+            | OutputFormat::Pcm44100
+            | OutputFormat::Pcm48000 => AudioEncoding::Pcm,
+            OutputFormat::Ulaw8000 => AudioEncoding::Ulaw,
+            OutputFormat::Alaw8000 => AudioEncoding::Alaw,
+            OutputFormat::Opus48000_32
+            | OutputFormat::Opus48000_64
+            | OutputFormat::Opus48000_96
+            | OutputFormat::Opus48000_128
+            | OutputFormat::Opus48000_192 => AudioEncoding::Opus,
+        }
+    }
+
+    /// Returns the sample rate of the audio in Hz.
+    pub fn sample_rate(&self) -> u32 {
+        match self {
+            OutputFormat::Pcm8000 | OutputFormat::Ulaw8000 | OutputFormat::Alaw8000 => 8_000,
+            OutputFormat::Pcm16000 => 16_000,
+            OutputFormat::Mp3_22050_32 | OutputFormat::Pcm22050 => 22_050,
+            OutputFormat::Pcm24000 => 24_000,
+            OutputFormat::Mp3_44100_32
+            | OutputFormat::Mp3_44100_64
+            | OutputFormat::Mp3_44100_96
+            | OutputFormat::Mp3_44100_128
+            | OutputFormat::Mp3_44100_192
+            | OutputFormat::Pcm44100 => 44_100,
+            OutputFormat::Pcm48000
+            | OutputFormat::Opus48000_32
+            | OutputFormat::Opus48000_64
+            | OutputFormat::Opus48000_96
+            | OutputFormat::Opus48000_128
+            | OutputFormat::Opus48000_192 => 48_000,
+        }
+    }
+
+    /// Returns the number of audio channels. Every format the API produces is mono.
+    pub fn channels(&self) -> u16 {
+        1
+    }
+
+    /// Returns the bitrate in kbit/s of compressed formats, or `None` for PCM, μ-law and A-law.
+    pub fn bitrate_kbps(&self) -> Option<u32> {
+        match self {
+            OutputFormat::Mp3_22050_32 | OutputFormat::Mp3_44100_32 | OutputFormat::Opus48000_32 => Some(32),
+            OutputFormat::Mp3_44100_64 | OutputFormat::Opus48000_64 => Some(64),
+            OutputFormat::Mp3_44100_96 | OutputFormat::Opus48000_96 => Some(96),
+            OutputFormat::Mp3_44100_128 | OutputFormat::Opus48000_128 => Some(128),
+            OutputFormat::Mp3_44100_192 | OutputFormat::Opus48000_192 => Some(192),
+            _ => None,
+        }
+    }
+
+    /// Returns the MIME type of the audio, suitable for an `Accept` header.
+    pub fn mime_type(&self) -> &'static str {
+        match self.encoding() {
+            AudioEncoding::Mp3 => "audio/mpeg",
+            AudioEncoding::Pcm => "audio/pcm",
+            AudioEncoding::Ulaw => "audio/basic",
+            AudioEncoding::Alaw => "audio/x-alaw-basic",
+            AudioEncoding::Opus => "audio/ogg",
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OutputFormat::ALL
+            .iter()
+            .find(|format| format.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown output format `{}`", s))
+    }
+}
+
+/// Audio returned by the API, together with the format it is encoded in.
+#[derive(Debug, Clone)]
+pub struct Audio {
+    pub data: Bytes,
+    pub format: OutputFormat,
+    /// The ID the API assigned to the request that produced the audio.
+    pub request_id: Option<String>,
+}
+
+impl Audio {
+    /// Returns the encoding of the audio.
+    pub fn encoding(&self) -> AudioEncoding {
+        self.format.encoding()
+    }
+
+    /// Returns the sample rate of the audio in Hz.
+    pub fn sample_rate(&self) -> u32 {
+        self.format.sample_rate()
+    }
+
+    /// Returns the number of audio channels.
+    pub fn channels(&self) -> u16 {
+        self.format.channels()
+    }
+
+    /// Returns the encoded audio.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Joins consecutive clips of the same format into one.
+    ///
+    /// Raw formats (PCM, μ-law, A-law) are appended sample by sample. MP3 clips are
+    /// appended frame by frame, dropping the ID3 tag of every clip but the first. Opus
+    /// clips are appended as a chained Ogg stream, which players decode as one file.
+    pub fn concat(clips: Vec<Audio>) -> Result<Audio, Error> {
+        let format = match clips.first() {
+            Some(first) => first.format,
+            None => return Err(Error::InvalidRequest("no audio to concatenate".to_string())),
+        };
+        if let Some(other) = clips.iter().find(|clip| clip.format != format) {
+            return Err(Error::InvalidRequest(format!(
+                "cannot concatenate {} audio with {} audio",
+                format, other.format
+            )));
+        }
+
+        let request_id = clips.last().and_then(|clip| clip.request_id.clone());
+        let mut data = BytesMut::with_capacity(clips.iter().map(|clip| clip.data.len()).sum());
+        for (index, clip) in clips.iter().enumerate() {
+            if index > 0 && format.encoding() == AudioEncoding::Mp3 {
+                data.extend_from_slice(strip_id3v2(&clip.data));
+            } else {
+                data.extend_from_slice(&clip.data);
+            }
+        }
+
+        Ok(Audio {
+            data: data.freeze(),
+            format,
+            request_id,
+        })
+    }
+}
+
+/// Returns `data` without its leading ID3v2 tag, if it has one.
+fn strip_id3v2(data: &[u8]) -> &[u8] {
+    if data.len() < 10 || &data[..3] != b"ID3" {
+        return data;
+    }
+
+    // The tag size is a 28-bit "synchsafe" integer that excludes the 10-byte header
+    // and the optional 10-byte footer.
+    let size = data[6..10].iter().fold(0usize, |size, byte| (size << 7) | (*byte as usize & 0x7f));
+    let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+
+    data.get(10 + size + footer..).unwrap_or_default()
+}
+
+/// A language a model can speak.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Language {
+    pub language_id: String,
+    pub name: String,
+}
+
+/// A model, together with what it can do and what it costs.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Model {
+    pub model_id: String,
+    #[serde(default)]
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub can_be_finetuned: bool,
+    #[serde(default)]
+    pub can_do_text_to_speech: bool,
+    #[serde(default)]
+    pub can_do_voice_conversion: bool,
+    #[serde(default)]
+    pub can_use_style: bool,
+    #[serde(default)]
+    pub can_use_speaker_boost: bool,
+    #[serde(default)]
+    pub serves_pro_voices: bool,
+    #[serde(default)]
+    pub requires_alpha_access: bool,
+    /// How many characters each character of text is billed as.
+    pub token_cost_factor: Option<f64>,
+    /// The most characters a single request may contain on the free tier.
+    pub max_characters_request_free_user: Option<usize>,
+    /// The most characters a single request may contain on paid tiers.
+    pub max_characters_request_subscribed_user: Option<usize>,
+    pub maximum_text_length_per_request: Option<usize>,
+    #[serde(default)]
+    pub languages: Vec<Language>,
+}
+
+impl Model {
+    /// Returns the most characters a single request may contain on a free (`subscribed ==
+    /// false`) or paid tier, if the API reports a limit.
+    pub fn max_characters(&self, subscribed: bool) -> Option<usize> {
+        let tier_limit = if subscribed {
+            self.max_characters_request_subscribed_user
+        } else {
+            self.max_characters_request_free_user
+        };
+        tier_limit.or(self.maximum_text_length_per_request)
+    }
+
+    /// Returns whether the model speaks the language with the given ID, e.g. `en`.
+    pub fn supports_language(&self, language_id: &str) -> bool {
+        self.languages.iter().any(|language| language.language_id == language_id)
+    }
+
+    /// Checks that the model can synthesize `characters` characters of text with `voice_settings`.
+    pub(crate) fn check_text_to_speech(
+        &self,
+        characters: usize,
+        voice_settings: Option<&VoiceSettings>,
+        subscribed: bool,
+    ) -> Result<(), Error> {
+        if !self.can_do_text_to_speech {
+            return Err(self.unsupported("text to speech"));
+        }
+        if let Some(limit) = self.max_characters(subscribed) {
+            if characters > limit {
+                return Err(Error::InvalidRequest(format!(
+                    "text has {} characters but {} accepts at most {} per request",
+                    characters, self.model_id, limit
+                )));
+            }
+        }
+        self.check_voice_settings(voice_settings)
+    }
+
+    /// Checks that the model can convert speech with `voice_settings`.
+    pub(crate) fn check_voice_conversion(&self, voice_settings: Option<&VoiceSettings>) -> Result<(), Error> {
+        if !self.can_do_voice_conversion {
+            return Err(self.unsupported("voice conversion"));
+        }
+        self.check_voice_settings(voice_settings)
+    }
+
+    fn check_voice_settings(&self, voice_settings: Option<&VoiceSettings>) -> Result<(), Error> {
+        let Some(voice_settings) = voice_settings else {
+            return Ok(());
+        };
+        if voice_settings.style.is_some_and(|style| style > 0.0) && !self.can_use_style {
+            return Err(self.unsupported("the style setting"));
+        }
+        if voice_settings.use_speaker_boost == Some(true) && !self.can_use_speaker_boost {
+            return Err(self.unsupported("speaker boost"));
+        }
+        Ok(())
+    }
+
+    fn unsupported(&self, capability: &str) -> Error {
+        Error::InvalidRequest(format!("model {} does not support {}", self.model_id, capability))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip(data: &'static [u8], format: OutputFormat, request_id: &str) -> Audio {
+        Audio {
+            data: Bytes::from_static(data),
+            format,
+            request_id: Some(request_id.to_string()),
+        }
+    }
+
+    /// Builds an ID3v2 header announcing a tag of `size` bytes.
+    fn id3_header(size: u32, flags: u8) -> Vec<u8> {
+        let mut header = b"ID3\x04\x00".to_vec();
+        header.push(flags);
+        header.extend((0..4).rev().map(|shift| ((size >> (7 * shift)) & 0x7f) as u8));
+        header
+    }
+
+    #[test]
+    fn strip_id3v2_removes_the_tag() {
+        let mut data = id3_header(3, 0);
+        data.extend_from_slice(b"tagFRAMES");
+
+        assert_eq!(strip_id3v2(&data), b"FRAMES");
+    }
+
+    #[test]
+    fn strip_id3v2_decodes_synchsafe_sizes_and_footers() {
+        let mut data = id3_header(200, 0x10);
+        data.resize(data.len() + 200 + 10, 0);
+        data.extend_from_slice(b"FRAMES");
+
+        assert_eq!(strip_id3v2(&data), b"FRAMES");
+    }
+
+    #[test]
+    fn strip_id3v2_keeps_untagged_and_truncated_data() {
+        assert_eq!(strip_id3v2(b"\xff\xfbFRAMES"), b"\xff\xfbFRAMES");
+        assert_eq!(strip_id3v2(b"ID3"), b"ID3");
+
+        let truncated = id3_header(100, 0);
+        assert!(strip_id3v2(&truncated).is_empty());
+    }
+
+    #[test]
+    fn concat_strips_id3_tags_after_the_first_mp3_clip() {
+        let mut tagged = id3_header(3, 0);
+        tagged.extend_from_slice(b"tagSECOND");
+        let first = clip(b"FIRST", OutputFormat::Mp3_44100_128, "a");
+        let second = Audio {
+            data: Bytes::from(tagged),
+            ..clip(b"", OutputFormat::Mp3_44100_128, "b")
+        };
+
+        let audio = Audio::concat(vec![first, second]).unwrap();
+        assert_eq!(audio.as_bytes(), b"FIRSTSECOND");
+        assert_eq!(audio.format, OutputFormat::Mp3_44100_128);
+        assert_eq!(audio.request_id.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn concat_appends_raw_audio_unchanged() {
+        let audio = Audio::concat(vec![
+            clip(b"ID3\x04\x00\x00\x00\x00\x00\x01x", OutputFormat::Pcm16000, "a"),
+            clip(b"ID3\x04\x00\x00\x00\x00\x00\x01y", OutputFormat::Pcm16000, "b"),
+        ])
+        .unwrap();
+
+        assert_eq!(audio.as_bytes(), b"ID3\x04\x00\x00\x00\x00\x00\x01xID3\x04\x00\x00\x00\x00\x00\x01y");
+    }
+
+    #[test]
+    fn concat_rejects_mixed_formats_and_empty_input() {
+        let mixed = Audio::concat(vec![
+            clip(b"a", OutputFormat::Mp3_44100_128, "a"),
+            clip(b"b", OutputFormat::Pcm16000, "b"),
+        ]);
+        assert!(matches!(mixed, Err(Error::InvalidRequest(_))));
+        assert!(matches!(Audio::concat(Vec::new()), Err(Error::InvalidRequest(_))));
+    }
+
+    fn parse_model(json: &str) -> Model {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn max_characters_prefers_the_tier_limit() {
+        let model = parse_model(
+            r#"{"model_id": "m", "max_characters_request_free_user": 2500,
+                "max_characters_request_subscribed_user": 10000, "maximum_text_length_per_request": 20000}"#,
+        );
+        assert_eq!(model.max_characters(false), Some(2500));
+        assert_eq!(model.max_characters(true), Some(10000));
+
+        let untiered = parse_model(r#"{"model_id": "m", "maximum_text_length_per_request": 20000}"#);
+        assert_eq!(untiered.max_characters(true), Some(20000));
+        assert_eq!(parse_model(r#"{"model_id": "m"}"#).max_characters(true), None);
+    }
+
+    #[test]
+    fn check_text_to_speech_rejects_what_the_model_cannot_serve() {
+        let model = parse_model(
+            r#"{"model_id": "m", "can_do_text_to_speech": true, "can_use_style": false,
+                "max_characters_request_free_user": 100}"#,
+        );
+        let styled = VoiceSettings::new(0.5, 0.5).unwrap().with_style(0.3).unwrap();
+
+        assert!(model.check_text_to_speech(100, None, false).is_ok());
+        assert!(matches!(model.check_text_to_speech(101, None, false), Err(Error::InvalidRequest(_))));
+        assert!(matches!(model.check_text_to_speech(10, Some(&styled), false), Err(Error::InvalidRequest(_))));
+        assert!(matches!(
+            parse_model(r#"{"model_id": "sts"}"#).check_text_to_speech(10, None, false),
+            Err(Error::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,566 @@
+//! W3C Pronunciation Lexicon Specification (PLS) lexicons, the file format of pronunciation
+//! dictionaries.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::api::pronunciation_dictionaries::{PhonemeAlphabet, PronunciationRule};
+use crate::error::Error;
+
+/// The namespace of PLS 1.0 documents.
+pub const PLS_NAMESPACE: &str = "http://www.w3.org/2005/01/pronunciation-lexicon";
+
+/// How the graphemes of a lexeme are pronounced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pronunciation {
+    /// The graphemes are replaced by this text before they are spoken.
+    Alias(String),
+    /// The graphemes are spoken as this phonetic transcription. Without an alphabet, the
+    /// alphabet of the lexicon applies.
+    Phoneme {
+        phoneme: String,
+        alphabet: Option<PhonemeAlphabet>,
+    },
+}
+
+/// An entry of a lexicon: one or more spellings and how they are pronounced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lexeme {
+    pub graphemes: Vec<String>,
+    pub pronunciation: Pronunciation,
+}
+
+/// A PLS lexicon.
+///
+/// # Examples
+///
+/// ```
+/// # use elevenlabs_rust::pls::Lexicon;
+/// # fn run() -> Result<(), elevenlabs_rust::Error> {
+/// let lexicon = Lexicon::parse(r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <lexicon version="1.0" xmlns="http://www.w3.org/2005/01/pronunciation-lexicon"
+///          alphabet="ipa" xml:lang="en-US">
+///   <lexeme><grapheme>UN</grapheme><alias>United Nations</alias></lexeme>
+///   <lexeme><grapheme>tomato</grapheme><phoneme>/tə'meɪtoʊ/</phoneme></lexeme>
+/// </lexicon>"#)?;
+/// assert_eq!(lexicon.lexemes.len(), 2);
+/// assert_eq!(Lexicon::parse(&lexicon.to_xml())?, lexicon);
+/// # Ok(())
+/// # }
+/// # run().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lexicon {
+    /// The language of the lexicon, e.g. `en-US`.
+    pub language: String,
+    /// The alphabet of phonemes that don't name their own.
+    pub alphabet: PhonemeAlphabet,
+    pub lexemes: Vec<Lexeme>,
+}
+
+/// The lexeme being read by [`Lexicon::parse`].
+#[derive(Default)]
+struct PartialLexeme {
+    graphemes: Vec<String>,
+    alias: Option<String>,
+    phoneme: Option<(String, Option<PhonemeAlphabet>)>,
+}
+
+/// The element whose text is being read by [`Lexicon::parse`].
+enum Field {
+    Grapheme,
+    Alias,
+    /// The first phoneme of a lexeme, with its alphabet.
+    Phoneme(Option<PhonemeAlphabet>),
+}
+
+/// The state of [`Lexicon::parse`].
+#[derive(Default)]
+struct Parser {
+    lexicon: Option<Lexicon>,
+    lexeme: Option<PartialLexeme>,
+    field: Option<Field>,
+    text: String,
+}
+
+impl Parser {
+    fn start(&mut self, element: &BytesStart) -> Result<(), Error> {
+        match element.local_name().as_ref() {
+            b"lexicon" => {
+                let alphabet = match attribute(element, b"alphabet")? {
+                    Some(alphabet) => parse_alphabet(&alphabet)?,
+                    None => PhonemeAlphabet::Ipa,
+                };
+                let language = attribute(element, b"xml:lang")?.unwrap_or_default();
+                self.lexicon = Some(Lexicon::new(&language, alphabet));
+            }
+            b"lexeme" => self.lexeme = Some(PartialLexeme::default()),
+            b"grapheme" => self.field = Some(Field::Grapheme),
+            b"alias" => self.field = Some(Field::Alias),
+            b"phoneme" if self.lexeme.as_ref().is_some_and(|lexeme| lexeme.phoneme.is_none()) => {
+                let alphabet = attribute(element, b"alphabet")?
+                    .map(|alphabet| parse_alphabet(&alphabet))
+                    .transpose()?;
+                self.field = Some(Field::Phoneme(alphabet));
+            }
+            _ => {}
+        }
+        self.text.clear();
+        Ok(())
+    }
+
+    fn end(&mut self, name: &[u8]) -> Result<(), Error> {
+        let text = std::mem::take(&mut self.text);
+        match (name, self.field.take(), self.lexeme.as_mut()) {
+            (b"grapheme", Some(Field::Grapheme), Some(_)) if text.is_empty() => {
+                return Err(Error::Lexicon("empty grapheme".to_string()));
+            }
+            (b"grapheme", Some(Field::Grapheme), Some(lexeme)) => lexeme.graphemes.push(text),
+            (b"alias", Some(Field::Alias), Some(lexeme)) if lexeme.alias.is_none() => lexeme.alias = Some(text),
+            (b"phoneme", Some(Field::Phoneme(_)), Some(_)) if text.is_empty() => {
+                return Err(Error::Lexicon("empty phoneme".to_string()));
+            }
+            (b"phoneme", Some(Field::Phoneme(alphabet)), Some(lexeme)) => lexeme.phoneme = Some((text, alphabet)),
+            (b"lexeme", _, _) => {
+                let lexicon = self
+                    .lexicon
+                    .as_mut()
+                    .ok_or_else(|| Error::Lexicon("lexeme outside of a lexicon".to_string()))?;
+                lexicon.lexemes.push(finish_lexeme(self.lexeme.take().unwrap_or_default())?);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl Lexicon {
+    /// Creates an empty lexicon.
+    pub fn new(language: &str, alphabet: PhonemeAlphabet) -> Self {
+        Lexicon {
+            language: language.to_string(),
+            alphabet,
+            lexemes: Vec::new(),
+        }
+    }
+
+    /// Creates a lexicon with one lexeme per rule.
+    pub fn from_rules(language: &str, alphabet: PhonemeAlphabet, rules: &[PronunciationRule]) -> Self {
+        let lexemes = rules
+            .iter()
+            .map(|rule| match rule {
+                PronunciationRule::Alias { string_to_replace, alias } => Lexeme {
+                    graphemes: vec![string_to_replace.clone()],
+                    pronunciation: Pronunciation::Alias(alias.clone()),
+                },
+                PronunciationRule::Phoneme {
+                    string_to_replace,
+                    phoneme,
+                    alphabet: rule_alphabet,
+                } => Lexeme {
+                    graphemes: vec![string_to_replace.clone()],
+                    pronunciation: Pronunciation::Phoneme {
+                        phoneme: phoneme.clone(),
+                        alphabet: Some(*rule_alphabet).filter(|rule_alphabet| *rule_alphabet != alphabet),
+                    },
+                },
+            })
+            .collect();
+
+        Lexicon {
+            language: language.to_string(),
+            alphabet,
+            lexemes,
+        }
+    }
+
+    /// Parses a PLS document.
+    ///
+    /// Only the first alias or phoneme of a lexeme is kept, and elements other than
+    /// `lexicon`, `lexeme`, `grapheme`, `alias` and `phoneme` are ignored. Self-closing
+    /// elements are read as empty ones: `<alias/>` is an empty alias, while an empty grapheme
+    /// or phoneme is rejected.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains the `Lexicon`, or `Error::Lexicon` if the document
+    /// is not a valid lexicon.
+    pub fn parse(xml: &str) -> Result<Lexicon, Error> {
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        let mut parser = Parser::default();
+
+        loop {
+            match reader.read_event().map_err(lexicon_error)? {
+                Event::Start(element) => parser.start(&element)?,
+                Event::Empty(element) => {
+                    parser.start(&element)?;
+                    parser.end(element.local_name().as_ref())?;
+                }
+                Event::Text(content) => parser.text.push_str(&content.unescape().map_err(lexicon_error)?),
+                Event::CData(content) => parser.text.push_str(&String::from_utf8_lossy(&content)),
+                Event::End(element) => parser.end(element.local_name().as_ref())?,
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        parser
+            .lexicon
+            .ok_or_else(|| Error::Lexicon("missing lexicon element".to_string()))
+    }
+
+    /// Writes the lexicon as a PLS document.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<lexicon version=\"1.0\" xmlns=\"{}\" alphabet=\"{}\" xml:lang=\"{}\">",
+            PLS_NAMESPACE,
+            alphabet_name(self.alphabet),
+            escape(&self.language)
+        );
+
+        for lexeme in &self.lexemes {
+            xml.push_str("  <lexeme>\n");
+            for grapheme in &lexeme.graphemes {
+                let _ = writeln!(xml, "    <grapheme>{}</grapheme>", escape(grapheme));
+            }
+            match &lexeme.pronunciation {
+                Pronunciation::Alias(alias) => {
+                    let _ = writeln!(xml, "    <alias>{}</alias>", escape(alias));
+                }
+                Pronunciation::Phoneme {
+                    phoneme,
+                    alphabet: Some(alphabet),
+                } => {
+                    let _ = writeln!(
+                        xml,
+                        "    <phoneme alphabet=\"{}\">{}</phoneme>",
+                        alphabet_name(*alphabet),
+                        escape(phoneme)
+                    );
+                }
+                Pronunciation::Phoneme { phoneme, alphabet: None } => {
+                    let _ = writeln!(xml, "    <phoneme>{}</phoneme>", escape(phoneme));
+                }
+            }
+            xml.push_str("  </lexeme>\n");
+        }
+
+        xml.push_str("</lexicon>\n");
+        xml
+    }
+
+    /// Returns the dictionary rules of the lexicon, one per grapheme. When a grapheme appears
+    /// in several lexemes, the last one wins.
+    pub fn rules(&self) -> Vec<PronunciationRule> {
+        let mut rules = BTreeMap::new();
+
+        for lexeme in &self.lexemes {
+            for grapheme in &lexeme.graphemes {
+                let rule = match &lexeme.pronunciation {
+                    Pronunciation::Alias(alias) => PronunciationRule::alias(grapheme, alias),
+                    Pronunciation::Phoneme { phoneme, alphabet } => {
+                        PronunciationRule::phoneme(grapheme, phoneme, alphabet.unwrap_or(self.alphabet))
+                    }
+                };
+                rules.insert(grapheme.clone(), rule);
+            }
+        }
+
+        rules.into_values().collect()
+    }
+}
+
+/// The changes that turn one set of rules into another.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RuleDiff {
+    /// Rules that are new or differ from the rule for the same string.
+    pub add: Vec<PronunciationRule>,
+    /// The strings whose rules must be removed.
+    pub remove: Vec<String>,
+}
+
+impl RuleDiff {
+    /// Compares the rules of a dictionary with the rules it should have.
+    ///
+    /// Adding a rule replaces the rule for the same string, so changed rules are only added.
+    pub fn between(current: &[PronunciationRule], target: &[PronunciationRule]) -> RuleDiff {
+        let current: BTreeMap<&str, &PronunciationRule> =
+            current.iter().map(|rule| (rule.string_to_replace(), rule)).collect();
+        let target: BTreeMap<&str, &PronunciationRule> =
+            target.iter().map(|rule| (rule.string_to_replace(), rule)).collect();
+
+        RuleDiff {
+            add: target
+                .iter()
+                .filter(|(string, rule)| current.get(*string) != Some(*rule))
+                .map(|(_, rule)| (*rule).clone())
+                .collect(),
+            remove: current
+                .keys()
+                .filter(|string| !target.contains_key(*string))
+                .map(|string| string.to_string())
+                .collect(),
+        }
+    }
+
+    /// Returns whether the rules are already the same.
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+}
+
+fn finish_lexeme(lexeme: PartialLexeme) -> Result<Lexeme, Error> {
+    if lexeme.graphemes.is_empty() {
+        return Err(Error::Lexicon("lexeme without a grapheme".to_string()));
+    }
+
+    let pronunciation = match (lexeme.alias, lexeme.phoneme) {
+        (Some(alias), _) => Pronunciation::Alias(alias),
+        (None, Some((phoneme, alphabet))) => Pronunciation::Phoneme { phoneme, alphabet },
+        (None, None) => {
+            return Err(Error::Lexicon(format!(
+                "lexeme {} has neither an alias nor a phoneme",
+                lexeme.graphemes[0]
+            )))
+        }
+    };
+
+    Ok(Lexeme {
+        graphemes: lexeme.graphemes,
+        pronunciation,
+    })
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Result<Option<String>, Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(lexicon_error)?;
+        if attribute.key.as_ref() == name {
+            return Ok(Some(attribute.unescape_value().map_err(lexicon_error)?.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+fn parse_alphabet(alphabet: &str) -> Result<PhonemeAlphabet, Error> {
+    match alphabet {
+        "ipa" => Ok(PhonemeAlphabet::Ipa),
+        "cmu-arpabet" | "x-cmu" | "x-arpabet" => Ok(PhonemeAlphabet::CmuArpabet),
+        other => Err(Error::Lexicon(format!("unsupported alphabet {}", other))),
+    }
+}
+
+fn alphabet_name(alphabet: PhonemeAlphabet) -> &'static str {
+    match alphabet {
+        PhonemeAlphabet::Ipa => "ipa",
+        PhonemeAlphabet::CmuArpabet => "cmu-arpabet",
+    }
+}
+
+fn lexicon_error(err: impl std::fmt::Display) -> Error {
+    Error::Lexicon(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon(body: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<lexicon version="1.0" xmlns="{}" alphabet="ipa" xml:lang="en-GB">{}</lexicon>"#,
+            PLS_NAMESPACE, body
+        )
+    }
+
+    #[test]
+    fn parses_aliases_and_phonemes() {
+        let parsed = Lexicon::parse(&lexicon(
+            r#"<lexeme><grapheme>UN</grapheme><grapheme>U.N.</grapheme><alias>United Nations</alias></lexeme>
+               <lexeme><grapheme>tomato</grapheme><phoneme>təˈmɑːtəʊ</phoneme></lexeme>
+               <lexeme><grapheme>Nvidia</grapheme><phoneme alphabet="x-cmu">EH N V IH1 D IY0 AH0</phoneme></lexeme>"#,
+        ))
+        .unwrap();
+
+        assert_eq!(parsed.language, "en-GB");
+        assert_eq!(parsed.alphabet, PhonemeAlphabet::Ipa);
+        assert_eq!(
+            parsed.lexemes,
+            [
+                Lexeme {
+                    graphemes: vec!["UN".to_string(), "U.N.".to_string()],
+                    pronunciation: Pronunciation::Alias("United Nations".to_string()),
+                },
+                Lexeme {
+                    graphemes: vec!["tomato".to_string()],
+                    pronunciation: Pronunciation::Phoneme {
+                        phoneme: "təˈmɑːtəʊ".to_string(),
+                        alphabet: None,
+                    },
+                },
+                Lexeme {
+                    graphemes: vec!["Nvidia".to_string()],
+                    pronunciation: Pronunciation::Phoneme {
+                        phoneme: "EH N V IH1 D IY0 AH0".to_string(),
+                        alphabet: Some(PhonemeAlphabet::CmuArpabet),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_through_xml() {
+        let mut original = Lexicon::new("en-US", PhonemeAlphabet::CmuArpabet);
+        original.lexemes = vec![
+            Lexeme {
+                graphemes: vec!["AT&T".to_string()],
+                pronunciation: Pronunciation::Alias("A T <and> T".to_string()),
+            },
+            Lexeme {
+                graphemes: vec!["read".to_string(), "reed".to_string()],
+                pronunciation: Pronunciation::Phoneme {
+                    phoneme: "R IY1 D".to_string(),
+                    alphabet: None,
+                },
+            },
+            Lexeme {
+                graphemes: vec!["\"quoted\"".to_string()],
+                pronunciation: Pronunciation::Phoneme {
+                    phoneme: "ˈkwoʊtɪd".to_string(),
+                    alphabet: Some(PhonemeAlphabet::Ipa),
+                },
+            },
+        ];
+
+        let xml = original.to_xml();
+        assert!(xml.contains("AT&amp;T"));
+        assert_eq!(Lexicon::parse(&xml).unwrap(), original);
+    }
+
+    #[test]
+    fn reads_cdata_and_ignores_unknown_elements() {
+        let parsed = Lexicon::parse(&lexicon(
+            r#"<meta name="author"/><lexeme><grapheme><![CDATA[R&D]]></grapheme><example>x</example>
+               <alias>research and development</alias><alias>ignored</alias></lexeme>"#,
+        ))
+        .unwrap();
+
+        assert_eq!(parsed.lexemes[0].graphemes, ["R&D"]);
+        assert_eq!(parsed.lexemes[0].pronunciation, Pronunciation::Alias("research and development".to_string()));
+    }
+
+    #[test]
+    fn reads_self_closing_elements() {
+        let empty = Lexicon::parse(r#"<lexicon version="1.0" alphabet="cmu-arpabet" xml:lang="de"/>"#).unwrap();
+        assert_eq!(empty, Lexicon::new("de", PhonemeAlphabet::CmuArpabet));
+
+        let parsed = Lexicon::parse(&lexicon(r#"<lexeme><grapheme>um</grapheme><alias/></lexeme>"#)).unwrap();
+        assert_eq!(parsed.lexemes[0].pronunciation, Pronunciation::Alias(String::new()));
+
+        let empty_phoneme = lexicon(r#"<lexeme><grapheme>GIF</grapheme><phoneme alphabet="ipa"/></lexeme>"#);
+        assert!(matches!(Lexicon::parse(&empty_phoneme), Err(Error::Lexicon(_))));
+    }
+
+    #[test]
+    fn rejects_invalid_lexicons() {
+        for xml in [
+            "<root/>".to_string(),
+            r#"<lexicon alphabet="klingon"/>"#.to_string(),
+            lexicon("<lexeme><alias>no grapheme</alias></lexeme>"),
+            lexicon("<lexeme><grapheme>no pronunciation</grapheme></lexeme>"),
+            lexicon("<lexeme><grapheme/><alias>empty grapheme</alias></lexeme>"),
+            lexicon("<lexeme/>"),
+            "<lexeme><grapheme>a</grapheme><alias>b</alias></lexeme>".to_string(),
+            lexicon("<lexeme><grapheme>unclosed</grapheme>"),
+        ] {
+            assert!(matches!(Lexicon::parse(&xml), Err(Error::Lexicon(_))), "accepted {}", xml);
+        }
+    }
+
+    #[test]
+    fn rules_expand_graphemes_and_resolve_the_alphabet() {
+        let parsed = Lexicon::parse(&lexicon(
+            r#"<lexeme><grapheme>UN</grapheme><grapheme>U.N.</grapheme><alias>United Nations</alias></lexeme>
+               <lexeme><grapheme>tomato</grapheme><phoneme>təˈmɑːtəʊ</phoneme></lexeme>
+               <lexeme><grapheme>UN</grapheme><alias>you en</alias></lexeme>"#,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            parsed.rules(),
+            [
+                PronunciationRule::alias("U.N.", "United Nations"),
+                PronunciationRule::alias("UN", "you en"),
+                PronunciationRule::phoneme("tomato", "təˈmɑːtəʊ", PhonemeAlphabet::Ipa),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_rules_round_trips_rules() {
+        let rules = vec![
+            PronunciationRule::phoneme("Nvidia", "EH N V IH1 D IY0 AH0", PhonemeAlphabet::CmuArpabet),
+            PronunciationRule::alias("UN", "United Nations"),
+            PronunciationRule::phoneme("tomato", "təˈmɑːtəʊ", PhonemeAlphabet::Ipa),
+        ];
+
+        let lexicon = Lexicon::from_rules("en-US", PhonemeAlphabet::Ipa, &rules);
+        let reparsed = Lexicon::parse(&lexicon.to_xml()).unwrap();
+        assert_eq!(reparsed.rules(), rules);
+    }
+
+    #[test]
+    fn diff_adds_new_and_changed_rules_and_removes_stale_ones() {
+        let current = [
+            PronunciationRule::alias("UN", "United Nations"),
+            PronunciationRule::alias("EU", "European Union"),
+            PronunciationRule::phoneme("tomato", "təˈmeɪtoʊ", PhonemeAlphabet::Ipa),
+        ];
+        let target = [
+            PronunciationRule::alias("UN", "United Nations"),
+            PronunciationRule::phoneme("tomato", "təˈmɑːtəʊ", PhonemeAlphabet::Ipa),
+            PronunciationRule::alias("NATO", "nay toe"),
+        ];
+
+        let diff = RuleDiff::between(&current, &target);
+        assert_eq!(
+            diff.add,
+            [
+                PronunciationRule::alias("NATO", "nay toe"),
+                PronunciationRule::phoneme("tomato", "təˈmɑːtəʊ", PhonemeAlphabet::Ipa),
+            ]
+        );
+        assert_eq!(diff.remove, ["EU"]);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn diff_of_equal_rules_is_empty() {
+        let rules = [
+            PronunciationRule::alias("UN", "United Nations"),
+            PronunciationRule::phoneme("GIF", "dʒɪf", PhonemeAlphabet::Ipa),
+        ];
+        let reordered = [rules[1].clone(), rules[0].clone()];
+
+        assert!(RuleDiff::between(&rules, &reordered).is_empty());
+        assert_eq!(RuleDiff::between(&rules, &[]).remove, ["GIF", "UN"]);
+    }
+
+    #[test]
+    fn diff_treats_a_changed_rule_type_as_a_change() {
+        let current = [PronunciationRule::alias("GIF", "jif")];
+        let target = [PronunciationRule::phoneme("GIF", "ɡɪf", PhonemeAlphabet::Ipa)];
+
+        let diff = RuleDiff::between(&current, &target);
+        assert_eq!(diff.add, target);
+        assert!(diff.remove.is_empty());
+    }
+}